@@ -1,4 +1,5 @@
-use cosmwasm_std::{Decimal, StdError};
+use crate::types::{MarketStatus, PauseScope, Permission};
+use cosmwasm_std::{CheckedMultiplyRatioError, Decimal, OverflowError, StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,6 +7,12 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    CheckedMultiplyRatio(#[from] CheckedMultiplyRatioError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -63,12 +70,21 @@ pub enum ContractError {
     #[error("Invalid position direction")]
     InvalidPositionDirection {},
 
+    #[error("Invalid order type")]
+    InvalidOrderType {},
+
     #[error("Invalid cw20 token")]
     Invalidcw20token {},
 
     #[error("Invalid order data")]
     InvalidOrderData {},
 
+    #[error("Invalid order metadata: {err_msg}")]
+    InvalidOrderMetadata {
+        field: Option<String>,
+        err_msg: String,
+    },
+
     #[error("Insufficient open amount to close")]
     InsufficientOpenPositionToClose {
         intended_close_amount: Decimal,
@@ -89,6 +105,111 @@ pub enum ContractError {
 
     #[error("Pool does not have enough liquidity")]
     InsufficientLiquidity {},
+
+    #[error("Reduce-only order would increase or flip position")]
+    ReduceOnlyViolation {},
+
+    #[error("Oracle price is stale and no fallback price is available")]
+    StalePrice {},
+
+    #[error("Notional exposure exceeds the cap of the account tier")]
+    ExposureCapExceeded { tier: u32, max_notional: Decimal },
+
+    #[error("Leverage exceeds the cap of the account tier")]
+    LeverageCapExceeded { tier: u32, max_leverage: Decimal },
+
+    #[error("Liquidation penalty curve must have points with strictly increasing shortfall")]
+    InvalidLiquidationPenaltyCurve {},
+
+    #[error("{scope} is paused")]
+    Paused { scope: PauseScope },
+
+    #[error("Denom mapping not found")]
+    MappingNotFound { denom: String },
+
+    #[error("Denom is in use by open positions")]
+    DenomInUse { denom: String },
+
+    #[error("Funding payment pair is in use by open positions")]
+    FundingPairInUse {
+        price_denom: String,
+        asset_denom: String,
+    },
+
+    #[error("Referral code already registered")]
+    ReferralCodeTaken { code: String },
+
+    #[error("Referral code not found")]
+    ReferralCodeNotFound { code: String },
+
+    #[error("Referrer already set")]
+    ReferrerAlreadySet {},
+
+    #[error("Margin can only be added to or removed from isolated positions")]
+    PositionNotIsolated {},
+
+    #[error("Removing margin would breach the initial margin requirement")]
+    RemoveMarginWouldBreachInitialMargin { max_removable: Decimal },
+
+    #[error("Order would exceed the open interest cap of {max_open_interest}")]
+    OpenInterestCapExceeded { max_open_interest: Decimal },
+
+    #[error("Funding rate floor must not be greater than the funding rate cap")]
+    InvalidFundingRateClamp {},
+
+    #[error("Position limit of tier {tier} exceeded: max notional {limit}")]
+    PositionLimitExceeded { tier: u32, limit: Decimal },
+
+    #[error("Operator lacks the {required} permission")]
+    OperatorNotPermitted { required: Permission },
+
+    #[error("Invalid recipient address: {recipient}")]
+    InvalidRecipient { recipient: String },
+
+    #[error("Withdrawal {id} not found")]
+    WithdrawalNotFound { id: u64 },
+
+    #[error("Withdrawal can't be claimed before {claimable_at}")]
+    WithdrawalNotClaimable { claimable_at: u64 },
+
+    #[error("Withdrawal limit of {denom} exceeded: {remaining} left this epoch")]
+    WithdrawalLimitExceeded { denom: String, remaining: Uint128 },
+
+    #[error("Oracle price of {denom} is out of range")]
+    InvalidOraclePrice { denom: String },
+
+    #[error("Unsupported sudo message version {version}")]
+    UnsupportedSudoMsgVersion { version: u32 },
+
+    #[error("Invalid sudo message: {err_msg}")]
+    InvalidSudoMsg { err_msg: String },
+
+    #[error("Market is {status}")]
+    MarketNotActive { status: MarketStatus },
+
+    #[error("Price is not a multiple of the tick size {tick_size}")]
+    InvalidTickSize { tick_size: Decimal },
+
+    #[error("Quantity is not a multiple of the lot size {lot_size}")]
+    InvalidLotSize { lot_size: Decimal },
+
+    #[error("Order notional is below the minimum of {min_notional}")]
+    BelowMinNotional { min_notional: Decimal },
+
+    #[error("Invalid {field} value {value}")]
+    InvalidEnumValue { field: String, value: i32 },
+
+    #[error("Unknown is not a valid {field}")]
+    UnknownEnumValue { field: String },
+
+    #[error("Malformed denom: {denom}")]
+    MalformedDenom { denom: String },
+
+    #[error("Invalid market id: {market_id}")]
+    InvalidMarketId { market_id: String },
+
+    #[error("Allocation weights must be non-zero in total and all of the same sign")]
+    InvalidAllocationWeights {},
     // Add any other custom errors you like here.
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
 }
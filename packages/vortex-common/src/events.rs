@@ -0,0 +1,122 @@
+use cosmwasm_std::{Decimal, Event};
+
+use crate::{
+    msg::DecimalCoin,
+    types::{Fill, Order, Pair, PositionDirection},
+    utils::SignedDecimal,
+};
+
+// every typed event emitted by Vortex contracts carries a monotonically increasing sequence number
+// under this attribute key, so that indexers can detect missed events and resync deterministically
+pub const EVENT_SEQUENCE_KEY: &str = "sequence";
+
+pub fn with_sequence(event: Event, sequence: u64) -> Event {
+    event.add_attribute(EVENT_SEQUENCE_KEY, sequence.to_string())
+}
+
+pub fn get_sequence(event: &Event) -> Option<u64> {
+    event
+        .attributes
+        .iter()
+        .find(|attr| attr.key == EVENT_SEQUENCE_KEY)
+        .and_then(|attr| attr.value.parse::<u64>().ok())
+}
+
+// standardized events. Attribute keys and value formats are part of the indexer-facing API, so they
+// must stay stable across contract versions; add new attributes rather than changing existing ones.
+// Every constructor takes the event's sequence number, see `with_sequence`.
+pub const ORDER_PLACED_EVENT: &str = "order_placed";
+pub const ORDER_FILLED_EVENT: &str = "order_filled";
+pub const POSITION_LIQUIDATED_EVENT: &str = "position_liquidated";
+pub const FUNDING_PAID_EVENT: &str = "funding_paid";
+pub const DEPOSIT_EVENT: &str = "deposit";
+pub const WITHDRAW_EVENT: &str = "withdraw";
+
+fn with_pair(event: Event, pair: &Pair) -> Event {
+    event
+        .add_attribute("price_denom", &pair.price_denom)
+        .add_attribute("asset_denom", &pair.asset_denom)
+}
+
+pub fn order_placed(order: &Order, sequence: u64) -> Event {
+    let pair = Pair {
+        price_denom: order.price_denom.clone(),
+        asset_denom: order.asset_denom.clone(),
+    };
+    let event = with_pair(Event::new(ORDER_PLACED_EVENT), &pair)
+        .add_attribute("order_id", order.id.to_string())
+        .add_attribute("account", &order.account)
+        .add_attribute("direction", order.direction.to_string())
+        .add_attribute("order_type", order.order_type.to_string())
+        .add_attribute("effect", order.effect.to_string())
+        .add_attribute("price", order.price.to_string())
+        .add_attribute("quantity", order.quantity.to_string())
+        .add_attribute("leverage", order.leverage.to_string());
+    with_sequence(event, sequence)
+}
+
+pub fn order_filled(fill: &Fill, sequence: u64) -> Event {
+    let event = with_pair(Event::new(ORDER_FILLED_EVENT), &fill.pair)
+        .add_attribute("order_id", fill.order_id.to_string())
+        .add_attribute("account", &fill.account)
+        .add_attribute("direction", fill.direction.to_string())
+        .add_attribute("price", fill.price.to_string())
+        .add_attribute("quantity", fill.quantity.to_string())
+        .add_attribute("fee_denom", &fill.fee.denom)
+        .add_attribute("fee_amount", fill.fee.amount.to_string())
+        .add_attribute("maker", fill.maker.to_string())
+        .add_attribute("epoch", fill.epoch.to_string());
+    with_sequence(event, sequence)
+}
+
+pub fn position_liquidated(
+    account: &str,
+    pair: &Pair,
+    direction: PositionDirection,
+    quantity: Decimal,
+    price: Decimal,
+    liquidator: &str,
+    sequence: u64,
+) -> Event {
+    let event = with_pair(Event::new(POSITION_LIQUIDATED_EVENT), pair)
+        .add_attribute("account", account)
+        .add_attribute("direction", direction.to_string())
+        .add_attribute("quantity", quantity.to_string())
+        .add_attribute("price", price.to_string())
+        .add_attribute("liquidator", liquidator);
+    with_sequence(event, sequence)
+}
+
+// a positive `amount` was paid by the position, a negative one was received
+pub fn funding_paid(
+    account: &str,
+    pair: &Pair,
+    direction: PositionDirection,
+    amount: SignedDecimal,
+    epoch: i64,
+    sequence: u64,
+) -> Event {
+    let event = with_pair(Event::new(FUNDING_PAID_EVENT), pair)
+        .add_attribute("account", account)
+        .add_attribute("direction", direction.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("epoch", epoch.to_string());
+    with_sequence(event, sequence)
+}
+
+pub fn deposit(account: &str, coin: &DecimalCoin, sequence: u64) -> Event {
+    let event = Event::new(DEPOSIT_EVENT)
+        .add_attribute("account", account)
+        .add_attribute("denom", &coin.denom)
+        .add_attribute("amount", coin.amount.to_string());
+    with_sequence(event, sequence)
+}
+
+pub fn withdraw(account: &str, recipient: &str, coin: &DecimalCoin, sequence: u64) -> Event {
+    let event = Event::new(WITHDRAW_EVENT)
+        .add_attribute("account", account)
+        .add_attribute("recipient", recipient)
+        .add_attribute("denom", &coin.denom)
+        .add_attribute("amount", coin.amount.to_string());
+    with_sequence(event, sequence)
+}
@@ -1,4 +1,8 @@
 pub mod error;
+pub mod events;
 pub mod msg;
+#[cfg(feature = "protobuf")]
+pub mod proto;
+pub mod schema;
 pub mod types;
 pub mod utils;
@@ -1,16 +1,29 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 use crate::{
     error::ContractError,
     types::{
-        i32_to_direction, i32_to_order_type, MarginRatios, Order, OrderType, PositionDirection,
-        PositionEffect,
+        AccountTier, AdlQueueEntry, CancelAfter, CancelOnDisconnect, CumulativeFundingRate,
+        DenomDirection, EquitySnapshot, FeeTier, Fill, FundingRateClamp, FundingSettlementConfig,
+        FundingSnapshot, LiquidationPenaltyCurve, LiquiditySide, MarginMode, MarginRatios, Market,
+        MarketFeeOverride, MarketId, MarketStatus, OcoGroup, OperatorGrant, Order,
+        OrderBookSnapshot, OrderPlacementStatus, OrderType, PageRequest, PageResponse, Pair,
+        PauseScope, PendingWithdrawal, Permission, Position, PositionDirection, PositionEffect,
+        PositionLimitTier, PositionTrigger, RewardsConfig, SelfTradePrevention,
+        StakingDiscountTier, StalePricePolicy, StateSection, TimeInForce, TradeRecord, TwapOrder,
+        TwapOrderProgress, WithdrawalLimit,
     },
     utils::SignedDecimal,
 };
-use cosmwasm_std::{Addr, Coin, Decimal};
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Addr, Api, Binary, Coin, Decimal, Uint128};
+use cw2::ContractVersion;
 use cw20::Cw20ReceiveMsg;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
 use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -31,6 +44,13 @@ pub struct InstantiateMsg {
     pub limit_order_fee: SignedDecimal,
     pub market_order_fee: SignedDecimal,
     pub liquidation_order_fee: SignedDecimal,
+    // negative values are rebates
+    pub maker_fee: SignedDecimal,
+    pub taker_fee: SignedDecimal,
+    // share of the trading fees of referred accounts that is paid out to their referrer
+    pub referral_fee_share: Decimal,
+    // share of trading fees directed into the insurance fund
+    pub insurance_fund_fee_share: Decimal,
     pub max_leverage: SignedDecimal,
     pub funding_payment_lookback: u64,
     pub native_token: String,
@@ -38,19 +58,61 @@ pub struct InstantiateMsg {
     pub spot_market_contract: Addr,
     pub funding_payment_pairs: Vec<(String, String)>,
     pub default_margin_ratios: MarginRatios,
+    // applied to every funding payment pair without a pair-specific clamp. `None` leaves funding
+    // rates unclamped
+    pub default_funding_rate_clamp: Option<FundingRateClamp>,
+    // positions worth less than this at mark price are closed by `SettleDustPositions`
+    pub min_position_notional: Decimal,
+    // time between `RequestWithdrawal` and the earliest `ClaimWithdrawal`
+    pub withdrawal_delay_seconds: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
+// messages are deserialized once per call, so boxing the larger variants isn't worth the noise
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
     Deposit {},
     Withdraw {
         coins: Vec<Coin>,
     },
+    // withdraw the maximum amount that can be taken out without breaching margin requirements, for
+    // the given denoms or for every denom held if none are specified
+    WithdrawAll {
+        denoms: Option<Vec<String>>,
+    },
+    // like `Withdraw`, but the coins are sent to `recipient` instead of the sender
+    WithdrawTo {
+        recipient: String,
+        coins: Vec<Coin>,
+    },
+    // locks `coins` for the configured withdrawal delay; `recipient` defaults to the sender
+    RequestWithdrawal {
+        coins: Vec<Coin>,
+        recipient: Option<String>,
+    },
+    ClaimWithdrawal {
+        id: u64,
+    },
+    // a `max_per_epoch` of `None` removes the limit of `denom`
+    SetWithdrawalLimit {
+        denom: String,
+        max_per_epoch: Option<Uint128>,
+    },
+    // unlocks the coins of a pending withdrawal back into the account
+    CancelWithdrawal {
+        id: u64,
+    },
     WithdrawInsuranceFund {
         coin: Coin,
     },
+    // tops up the insurance fund with the sent funds; callable by anyone
+    DepositInsuranceFund {},
+    #[deprecated(note = "use `UpdateConfig`")]
+    UpdateInsuranceFundFeeShare {
+        insurance_fund_fee_share: Decimal,
+    },
     SwapMulticollateralToBase {
         orders: Vec<OrderPlacement>,
     },
@@ -78,43 +140,126 @@ pub enum ExecuteMsg {
     AddToFundingPaymentPairs {
         price_denom: String,
         asset_denom: String,
+        // defaults to `price_denom`
+        funding_settlement_denom: Option<String>,
+    },
+    UpdateFundingSettlementDenom {
+        price_denom: String,
+        asset_denom: String,
+        funding_settlement_denom: String,
+    },
+    UpdateFundingClamp {
+        price_denom: String,
+        asset_denom: String,
+        funding_rate_cap: SignedDecimal,
+        funding_rate_floor: SignedDecimal,
     },
     RemoveFromWhitelist {
         converter: String,
     },
+    RemoveFromFullDenomMapping {
+        full_denom: String,
+    },
+    RemoveFromOracleDenomMapping {
+        oracle_denom: String,
+    },
+    RemoveFromSupportedMultiCollateralDenoms {
+        denom: String,
+    },
+    RemoveFromFundingPaymentPairs {
+        price_denom: String,
+        asset_denom: String,
+    },
+    // correct an existing mapping in place; fails if the mapping doesn't exist
+    UpdateFullDenomMapping {
+        full_denom: String,
+        internal_denom: String,
+        conversion_rate: Decimal,
+    },
+    UpdateOracleDenomMapping {
+        oracle_denom: String,
+        internal_denom: String,
+        conversion_rate: Decimal,
+    },
     AddDenom {
         denom: String,
     },
     RemoveDenom {
         denom: String,
     },
+    #[deprecated(note = "use `UpdateConfig`")]
     UpdateMarginRatio {
         margin_ratio: MarginRatios,
     },
+    #[deprecated(note = "use `UpdateConfig`")]
     UpdateMaxLeverage {
         max_leverage: SignedDecimal,
     },
+    #[deprecated(note = "use `UpdateConfig`")]
     UpdateMarketOrderFee {
         market_order_fee: SignedDecimal,
     },
+    #[deprecated(note = "use `UpdateConfig`")]
     UpdateLimitOrderFee {
         limit_order_fee: SignedDecimal,
     },
+    #[deprecated(note = "use `UpdateConfig`")]
     UpdateLiquidationOrderFee {
         liquidation_order_fee: SignedDecimal,
     },
+    #[deprecated(note = "use `UpdateConfig`")]
+    UpdateMakerTakerFees {
+        maker_fee: SignedDecimal,
+        taker_fee: SignedDecimal,
+    },
+    SetFeeTiers {
+        tiers: Vec<FeeTier>,
+    },
+    #[deprecated(note = "use `UpdateConfig`")]
+    UpdateReferralFeeShare {
+        referral_fee_share: Decimal,
+    },
+    RegisterReferralCode {
+        code: String,
+    },
+    // can only be set once per account
+    SetReferrer {
+        code: String,
+    },
+    UpdateStakingDiscounts {
+        tiers: Vec<StakingDiscountTier>,
+    },
+    SetMarketFees {
+        price_denom: String,
+        asset_denom: String,
+        limit_order_fee: SignedDecimal,
+        market_order_fee: SignedDecimal,
+    },
+    #[deprecated(note = "use `UpdateConfig`")]
+    UpdateLiquidationPenaltyCurve {
+        curve: LiquidationPenaltyCurve,
+    },
+    #[deprecated(note = "use `UpdateConfig`")]
     UpdateAdmin {
         admin: String,
     },
+    #[deprecated(note = "use `UpdateConfig`")]
     UpdateFundingPaymentLookback {
         funding_payment_lookback: u64,
     },
+    #[deprecated(note = "use `UpdateConfig`")]
+    UpdateMinPositionNotional {
+        min_position_notional: Decimal,
+    },
+    #[deprecated(note = "use `UpdateConfig`")]
     UpdateNativeToken {
         native_token: String,
     },
+    #[deprecated(note = "use `UpdateConfig`")]
     UpdateBase {
         default_base: String,
     },
+    #[deprecated(note = "use `UpdateConfig`")]
     UpdateSpotMarketContract {
         contract_addr: String,
     },
@@ -133,6 +278,173 @@ pub enum ExecuteMsg {
         denom_name: String,
         denom_amount: u128,
     },
+    PlaceOcoOrders {
+        orders: Vec<OrderPlacement>,
+    },
+    UpdateRewardsConfig {
+        price_denom: String,
+        asset_denom: String,
+        config: RewardsConfig,
+    },
+    ClaimRewards {},
+    AmendOrder {
+        order_id: u64,
+        new_price: Option<Decimal>,
+        new_quantity: Option<Decimal>,
+    },
+    CancelOrders {
+        ids: Vec<u64>,
+    },
+    // cancel every resting order of the sender, optionally restricted to a single pair
+    CancelAllOrders {
+        price_denom: Option<String>,
+        asset_denom: Option<String>,
+    },
+    // registers or refreshes the sender's dead man's switch; a ttl of 0 disables it
+    SetCancelOnDisconnect {
+        ttl_epochs: u64,
+    },
+    // acts as a heartbeat; `seconds` of 0 disarms the switch
+    SetCancelAfter {
+        seconds: u64,
+    },
+    // with `atomic` set, the whole batch is rejected if any single order fails margin checks
+    PlaceOrders {
+        orders: Vec<OrderPlacement>,
+        atomic: bool,
+    },
+    UpdateStalePricePolicy {
+        price_denom: String,
+        asset_denom: String,
+        policy: StalePricePolicy,
+    },
+    SetAccountTiers {
+        tiers: Vec<AccountTier>,
+    },
+    // only callable by the risk manager
+    AssignAccountTier {
+        account: String,
+        tier: u32,
+    },
+    SetPositionLimitTiers {
+        tiers: Vec<PositionLimitTier>,
+    },
+    // accounts not assigned to any tier are not subject to position limits
+    AssignPositionLimitTier {
+        account: String,
+        tier: Option<u32>,
+    },
+    #[deprecated(note = "use `UpdateConfig`")]
+    UpdateRiskManager {
+        risk_manager: String,
+    },
+    Pause {
+        scope: PauseScope,
+    },
+    Unpause {
+        scope: PauseScope,
+    },
+    // applies to the sender's positions in `pair`; only allowed while the account has none open
+    SetMarginMode {
+        pair: Pair,
+        mode: MarginMode,
+    },
+    AddMargin {
+        pair: Pair,
+        direction: PositionDirection,
+        coin: Coin,
+    },
+    RemoveMargin {
+        pair: Pair,
+        direction: PositionDirection,
+        amount: Decimal,
+    },
+    // closes `quantity` (the whole position if unset) of the sender's position with a market order.
+    // `slippage_tolerance` is a fraction of the current mark price.
+    ClosePosition {
+        price_denom: String,
+        asset_denom: String,
+        direction: PositionDirection,
+        quantity: Option<Decimal>,
+        slippage_tolerance: Option<Decimal>,
+    },
+    // setting both prices to `None` removes the trigger
+    SetPositionTrigger {
+        pair: Pair,
+        direction: PositionDirection,
+        take_profit: Option<Decimal>,
+        stop_loss: Option<Decimal>,
+    },
+    // `None` removes the cap
+    SetOpenInterestCap {
+        price_denom: String,
+        asset_denom: String,
+        max_open_interest: Option<Decimal>,
+    },
+    // replaces any permissions previously granted to `operator`
+    GrantOperator {
+        operator: String,
+        permissions: Permission,
+    },
+    RevokeOperator {
+        operator: String,
+    },
+    // applies every set field of `updates` in a single transaction
+    UpdateConfig {
+        updates: ConfigUpdate,
+    },
+    // fails if a market for the pair already exists
+    AddMarket {
+        market: Market,
+    },
+    UpdateMarket {
+        price_denom: String,
+        asset_denom: String,
+        updates: MarketUpdate,
+    },
+}
+
+// `addr_validate` checks the bech32 encoding and prefix of the chain the contract runs on
+pub fn validate_recipient(api: &dyn Api, recipient: &str) -> Result<Addr, ContractError> {
+    api.addr_validate(recipient)
+        .map_err(|_| ContractError::InvalidRecipient {
+            recipient: recipient.to_owned(),
+        })
+}
+
+// fields left as `None` keep the market's current value
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct MarketUpdate {
+    pub tick_size: Option<Decimal>,
+    pub lot_size: Option<Decimal>,
+    pub min_notional: Option<Decimal>,
+    pub max_leverage: Option<SignedDecimal>,
+    pub margin_ratios: Option<MarginRatios>,
+    pub funding_enabled: Option<bool>,
+    pub status: Option<MarketStatus>,
+}
+
+// fields left as `None` keep their current value
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct ConfigUpdate {
+    pub admin: Option<String>,
+    pub risk_manager: Option<String>,
+    pub limit_order_fee: Option<SignedDecimal>,
+    pub market_order_fee: Option<SignedDecimal>,
+    pub liquidation_order_fee: Option<SignedDecimal>,
+    pub maker_fee: Option<SignedDecimal>,
+    pub taker_fee: Option<SignedDecimal>,
+    pub referral_fee_share: Option<Decimal>,
+    pub insurance_fund_fee_share: Option<Decimal>,
+    pub liquidation_penalty_curve: Option<LiquidationPenaltyCurve>,
+    pub default_margin_ratios: Option<MarginRatios>,
+    pub max_leverage: Option<SignedDecimal>,
+    pub min_position_notional: Option<Decimal>,
+    pub withdrawal_delay_seconds: Option<u64>,
+    pub funding_payment_lookback: Option<u64>,
+    pub native_token: Option<String>,
+    pub default_base: Option<String>,
+    pub spot_market_contract: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -141,12 +453,93 @@ pub struct DecimalCoin {
     pub amount: Decimal,
 }
 
+// newest sudo wire format understood by this version of the crate
+pub const SUDO_MSG_VERSION: u32 = 1;
+
+impl SudoMsg {
+    // wrapping an envelope in another one would build a message `into_latest` rejects
+    pub fn versioned(msg: &SudoMsg) -> Result<SudoMsg, ContractError> {
+        if let SudoMsg::Versioned { .. } = msg {
+            return Err(ContractError::InvalidSudoMsg {
+                err_msg: "nested versioned envelope".to_owned(),
+            });
+        }
+        let encoded =
+            serde_json_wasm::to_vec(msg).map_err(|err| ContractError::FailedToSerialize {
+                err_msg: err.to_string(),
+            })?;
+        Ok(SudoMsg::Versioned {
+            version: SUDO_MSG_VERSION,
+            msg: Binary::from(encoded),
+        })
+    }
+
+    // unwraps a `Versioned` envelope and converts older wire formats to the current `SudoMsg`.
+    // A future format change adds a match arm here instead of requiring chain and contract to
+    // upgrade in lockstep.
+    pub fn into_latest(self) -> Result<SudoMsg, ContractError> {
+        let (version, msg) = match self {
+            SudoMsg::Versioned { version, msg } => (version, msg),
+            unversioned => return Ok(unversioned),
+        };
+        match version {
+            1 => match serde_json_wasm::from_slice::<SudoMsg>(msg.as_slice()) {
+                Ok(SudoMsg::Versioned { .. }) => Err(ContractError::InvalidSudoMsg {
+                    err_msg: "nested versioned envelope".to_owned(),
+                }),
+                Ok(inner) => Ok(inner),
+                Err(err) => Err(ContractError::InvalidSudoMsg {
+                    err_msg: err.to_string(),
+                }),
+            },
+            _ => Err(ContractError::UnsupportedSudoMsgVersion { version }),
+        }
+    }
+}
+
+// price pushed by the chain's oracle module. The quoted value is `price * 10^exponent`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OraclePrice {
+    pub denom: String,
+    pub price: Decimal,
+    pub exponent: i32,
+    // unix seconds
+    pub publish_time: u64,
+}
+
+impl OraclePrice {
+    // scales the atomics directly, so any negative exponent works: once 10^-exponent is beyond the
+    // 18 decimal places of `Decimal` the price rounds down to zero. A positive exponent only fails if
+    // the scaled price doesn't fit a `Decimal`.
+    pub fn scaled_price(&self) -> Result<Decimal, ContractError> {
+        let atomics = self.price.atomics();
+        let scale = 10u128
+            .checked_pow(self.exponent.unsigned_abs())
+            .map(Uint128::new);
+        let scaled = match scale {
+            Some(scale) if self.exponent >= 0 => atomics.checked_mul(scale).ok(),
+            None if self.exponent >= 0 => Some(atomics).filter(|atomics| atomics.is_zero()),
+            Some(scale) => Some(atomics / scale),
+            None => Some(Uint128::zero()),
+        };
+        scaled
+            .map(Decimal::new)
+            .ok_or(ContractError::InvalidOraclePrice {
+                denom: self.denom.to_owned(),
+            })
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum SudoMsg {
     Settlement {
         epoch: i64,
         entries: Vec<SettlementEntry>,
+        // set when resuming a settlement that did not fit in a single sudo call; the token is the
+        // one returned in the previous `SettlementResponse`
+        #[serde(default)]
+        continuation_token: Option<String>,
     },
 
     NewBlock {
@@ -169,153 +562,1205 @@ pub enum SudoMsg {
     FinalizeBlock {
         contract_order_results: Vec<ContractOrderResult>,
     },
+
+    ExpireOrders {
+        ids: Vec<u64>,
+    },
+
+    // the insurance fund can't cover `shortfall` of a bankrupt position in `pair`, so profitable
+    // counterparties are reduced following the ADL queue
+    AutoDeleverage {
+        pair: Pair,
+        shortfall: Decimal,
+    },
+
+    // force-closes the positions of `accounts` whose notional is below `min_position_notional`;
+    // positions above the minimum are left untouched
+    SettleDustPositions {
+        accounts: Vec<String>,
+    },
+
+    // pushed by the oracle module whenever new prices are available, ahead of order matching
+    OraclePrices {
+        prices: Vec<OraclePrice>,
+    },
+
+    // sent once per epoch after the epoch's last block, separately from `NewBlock`. Times are unix
+    // seconds so funding can be accrued over the epoch's actual duration.
+    EpochEnd {
+        epoch: i64,
+        epoch_start_time: u64,
+        epoch_end_time: u64,
+    },
+
+    // `msg` is a JSON encoded sudo message in the wire format of `version`. Unversioned messages
+    // are treated as version 1; see `SudoMsg::into_latest`.
+    Versioned {
+        version: u32,
+        msg: Binary,
+    },
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
 pub enum QueryMsg {
-    GetBalance {
+    #[returns(GetBalanceResponse)]
+    GetBalance { account: String, symbol: String },
+
+    #[returns(GetBalancesResponse)]
+    GetBalances {
         account: String,
-        symbol: String,
+        #[serde(default)]
+        page: PageRequest,
     },
 
-    GetBalances {
+    #[returns(GetWithdrawalLimitsResponse)]
+    GetWithdrawalLimits {
+        #[serde(default)]
+        page: PageRequest,
+    },
+
+    #[returns(GetPendingWithdrawalsResponse)]
+    GetPendingWithdrawals {
         account: String,
+        #[serde(default)]
+        page: PageRequest,
     },
 
+    #[returns(GetCumulativeFundingRateResponse)]
     GetCumulativeFundingPaymentRate {
         price_denom: String,
         asset_denom: String,
     },
 
+    #[returns(GetCumulativeFundingRatesResponse)]
+    GetCumulativeFundingRates {
+        #[serde(default)]
+        page: PageRequest,
+    },
+
+    #[returns(GetFundingRateClampResponse)]
+    GetFundingRateClamp {
+        price_denom: String,
+        asset_denom: String,
+    },
+
+    #[returns(GetFundingRateResponse)]
+    GetFundingRate {
+        price_denom: String,
+        asset_denom: String,
+    },
+
+    #[returns(GetFundingSettlementConfigResponse)]
+    GetFundingSettlementConfig {
+        price_denom: String,
+        asset_denom: String,
+    },
+
+    // total funding over (start_epoch, end_epoch], computed from the snapshots at the two bounds
+    #[returns(GetFundingOverWindowResponse)]
+    GetFundingOverWindow {
+        price_denom: String,
+        asset_denom: String,
+        start_epoch: i64,
+        end_epoch: i64,
+    },
+
+    #[returns(GetPositionResponse)]
     GetPosition {
         account: String,
         price_denom: String,
         asset_denom: String,
     },
 
+    #[returns(GetPositionsResponse)]
+    GetPositions {
+        account: String,
+        #[serde(default)]
+        page: PageRequest,
+    },
+
+    #[returns(GetOrderResponse)]
     GetOrder {
         account: String,
         price_denom: String,
         asset_denom: String,
     },
 
-    GetPortfolioSpecs {
-        account: String,
+    // orders of a market sorted by id, optionally restricted to one account
+    #[returns(GetOrdersResponse)]
+    GetOrders {
+        account: Option<String>,
+        price_denom: String,
+        asset_denom: String,
+        #[serde(default)]
+        page: PageRequest,
     },
 
-    GetInsuranceFundBalance {
-        denom: String,
+    #[returns(GetOrderByIdResponse)]
+    GetOrderById { id: u64 },
+
+    #[returns(GetPortfolioSpecsResponse)]
+    GetPortfolioSpecs { account: String },
+
+    // `page.start_after` is the last account of the previous page
+    #[returns(GetPortfolioSpecsBatchResponse)]
+    GetPortfolioSpecsBatch {
+        accounts: Vec<String>,
+        #[serde(default)]
+        page: PageRequest,
     },
 
-    GetOrderEstimate {
-        order: Order,
+    #[returns(GetInsuranceFundBalanceResponse)]
+    GetInsuranceFundBalance { denom: String },
+
+    #[returns(GetInsuranceFundBalancesResponse)]
+    GetInsuranceFundBalances {
+        #[serde(default)]
+        page: PageRequest,
     },
 
+    #[returns(GetOrderEstimateResponse)]
+    GetOrderEstimate { order: Order },
+
+    // estimates the orders as one batch, i.e. against the combined margin impact of all of them
+    #[returns(GetOrderEstimatesResponse)]
+    GetOrderEstimates { orders: Vec<Order> },
+
+    #[returns(GetConfigResponse)]
     GetConfig {},
-}
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
-pub struct GetBalanceResponse {
-    pub amount: SignedDecimal,
-}
+    #[returns(GetFeeScheduleResponse)]
+    GetFeeSchedule {},
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
-pub struct GetBalancesResponse {
-    pub symbols: Vec<String>,
-    pub amounts: Vec<SignedDecimal>,
-}
+    #[returns(GetMarketResponse)]
+    GetMarket {
+        price_denom: String,
+        asset_denom: String,
+    },
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
-pub struct GetPositionResponse {
-    pub long_position: SignedDecimal,
-    pub long_position_margin_debt: SignedDecimal,
-    pub long_position_last_funding_payment_epoch: i64,
-    pub long_position_pnl: SignedDecimal,
-    pub short_position: SignedDecimal,
-    pub short_position_margin_debt: SignedDecimal,
-    pub short_position_last_funding_payment_epoch: i64,
-    pub short_position_pnl: SignedDecimal,
-}
+    // same as `GetMarket`, with the market addressed by its id, e.g. "ATOM-USDC"
+    #[returns(GetMarketResponse)]
+    GetMarketById { market_id: MarketId },
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
-pub struct GetPortfolioSpecsResponse {
-    pub equity: SignedDecimal,
-    pub total_position_value: SignedDecimal,
-    pub buying_power: SignedDecimal,
-    pub unrealized_pnl: SignedDecimal,
-    pub leverage: SignedDecimal,
-    pub balance: SignedDecimal,
-}
+    #[returns(ListMarketsResponse)]
+    ListMarkets {
+        #[serde(default)]
+        page: PageRequest,
+    },
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
-pub struct GetInsuranceFundBalanceResponse {
-    pub balance: SignedDecimal,
-}
+    #[returns(GetLastEventSequenceResponse)]
+    GetLastEventSequence {},
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
-pub struct GetOrderResponse {
-    pub orders: Vec<Order>,
-}
+    #[returns(GetPendingSettlementResponse)]
+    GetPendingSettlement {},
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
-pub struct GetCumulativeFundingRateResponse {
-    pub price_diff: SignedDecimal,
-}
+    #[returns(GetOcoGroupResponse)]
+    GetOcoGroup { group_id: u64 },
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
-pub struct GetOrderEstimateResponse {
-    pub order_fee_estimate: SignedDecimal,
-    pub deposits_required: Coin,
-}
+    #[returns(GetPendingRewardsResponse)]
+    GetPendingRewards { account: String },
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
-pub struct GetConfigResponse {
-    pub admin: String,
-    pub whitelist: HashSet<Addr>,
-    pub use_whitelist: bool,
-    pub limit_order_fee: SignedDecimal,
-    pub market_order_fee: SignedDecimal,
-    pub liquidation_order_fee: SignedDecimal,
-    pub default_margin_ratios: MarginRatios,
-    pub max_leverage: SignedDecimal,
-    pub spot_market_contract: String,
-}
+    #[returns(GetOrderByClientIdResponse)]
+    GetOrderByClientId {
+        account: String,
+        client_order_id: String,
+    },
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
-pub struct BulkOrderPlacementsResponse {
-    pub unsuccessful_orders: Vec<UnsuccessfulOrder>,
-}
+    #[returns(GetAccountsByCollateralDenomResponse)]
+    GetAccountsByCollateralDenom {
+        denom: String,
+        #[serde(default)]
+        page: PageRequest,
+    },
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
-pub struct UnsuccessfulOrder {
-    pub id: u64,
-    pub reason: String,
-}
+    #[returns(GetCancelOnDisconnectResponse)]
+    GetCancelOnDisconnect { account: String },
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
-pub struct LiquidationResponse {
-    pub successful_accounts: Vec<String>,
-    pub liquidation_orders: Vec<OrderPlacement>,
-}
+    #[returns(GetCancelAfterResponse)]
+    GetCancelAfter { account: String },
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct SettlementEntry {
-    pub account: String,
-    pub price_denom: String,
-    pub asset_denom: String,
-    pub quantity: Decimal,
-    pub execution_cost_or_proceed: Decimal,
-    pub expected_cost_or_proceed: Decimal,
-    pub position_direction: PositionDirection,
-    pub order_type: OrderType,
-    pub order_id: u64,
-}
+    #[returns(GetEquityHistoryResponse)]
+    GetEquityHistory {
+        account: String,
+        start_epoch: i64,
+        end_epoch: i64,
+        #[serde(default)]
+        page: PageRequest,
+    },
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct OrderPlacement {
-    pub id: u64,
-    pub status: i32,
+    #[returns(GetStalePricePolicyResponse)]
+    GetStalePricePolicy {
+        price_denom: String,
+        asset_denom: String,
+    },
+
+    // each task list is paginated independently
+    #[returns(GetKeeperWorkResponse)]
+    GetKeeperWork {
+        #[serde(default)]
+        liquidatable_accounts: PageRequest,
+        #[serde(default)]
+        expired_orders: PageRequest,
+        #[serde(default)]
+        stale_funding_accounts: PageRequest,
+        #[serde(default)]
+        dust_position_accounts: PageRequest,
+    },
+
+    #[returns(GetExpiringOrdersResponse)]
+    GetExpiringOrders {
+        before_epoch: i64,
+        #[serde(default)]
+        page: PageRequest,
+    },
+
+    #[returns(ResolveDenomResponse)]
+    ResolveDenom {
+        denom: String,
+        direction: DenomDirection,
+    },
+
+    #[returns(GetTwapOrderProgressResponse)]
+    GetTwapOrderProgress { order_id: u64 },
+
+    #[returns(GetAccountTierResponse)]
+    GetAccountTier { account: String },
+
+    #[returns(GetAccountTiersResponse)]
+    GetAccountTiers {
+        #[serde(default)]
+        page: PageRequest,
+    },
+
+    #[returns(GetPositionLimitTierResponse)]
+    GetPositionLimitTier { account: String },
+
+    #[returns(GetPendingSudoResultsResponse)]
+    GetPendingSudoResults {},
+
+    #[returns(GetOpenInterestResponse)]
+    GetOpenInterest {
+        price_denom: String,
+        asset_denom: String,
+    },
+
+    #[returns(GetProtocolStatusResponse)]
+    GetProtocolStatus {},
+
+    #[returns(GetMarkPriceResponse)]
+    GetMarkPrice {
+        price_denom: String,
+        asset_denom: String,
+    },
+
+    #[returns(GetIndexPriceResponse)]
+    GetIndexPrice {
+        price_denom: String,
+        asset_denom: String,
+    },
+
+    #[returns(GetTwapResponse)]
+    GetTwap {
+        price_denom: String,
+        asset_denom: String,
+        lookback_seconds: u64,
+    },
+
+    // accounts whose margin ratio is below maintenance
+    #[returns(GetLiquidatableAccountsResponse)]
+    GetLiquidatableAccounts {
+        #[serde(default)]
+        page: PageRequest,
+    },
+
+    #[returns(GetAccountHealthResponse)]
+    GetAccountHealth { account: String },
+
+    #[returns(GetWhitelistResponse)]
+    GetWhitelist {
+        #[serde(default)]
+        page: PageRequest,
+    },
+
+    #[returns(GetWhitelistResponse)]
+    GetMulticollateralWhitelist {
+        #[serde(default)]
+        page: PageRequest,
+    },
+
+    #[returns(GetTradeHistoryResponse)]
+    GetTradeHistory {
+        account: String,
+        #[serde(default)]
+        page: PageRequest,
+    },
+
+    // settlement entries processed in `epoch`, paginated by their index within the epoch
+    #[returns(GetSettlementsResponse)]
+    GetSettlements {
+        epoch: i64,
+        #[serde(default)]
+        page: PageRequest,
+    },
+
+    #[returns(GetLiquidationPriceResponse)]
+    GetLiquidationPrice {
+        account: String,
+        price_denom: String,
+        asset_denom: String,
+        direction: PositionDirection,
+    },
+
+    // largest quantity the account can open given its buying power and open interest caps
+    #[returns(GetMaxOrderSizeResponse)]
+    GetMaxOrderSize {
+        account: String,
+        price_denom: String,
+        asset_denom: String,
+        direction: PositionDirection,
+        leverage: Decimal,
+    },
+
+    #[returns(GetVersionResponse)]
+    GetVersion {},
+
+    // raw key-value export of a section of the contract state for indexers and disaster recovery.
+    // Like every other list query, `page.start_after` is the `next_key` of the previous page, which
+    // encodes the raw storage key that page ended at.
+    #[returns(ExportStateResponse)]
+    ExportState {
+        section: StateSection,
+        #[serde(default)]
+        page: PageRequest,
+    },
+
+    // fees in effect for a market, i.e. its override if one is set and the global fees otherwise
+    #[returns(GetMarketFeesResponse)]
+    GetMarketFees {
+        price_denom: String,
+        asset_denom: String,
+    },
+
+    #[returns(GetAccountFeeTierResponse)]
+    GetAccountFeeTier { account: String },
+
+    #[returns(GetReferralStatsResponse)]
+    GetReferralStats { account: String },
+
+    #[returns(GetFeeDiscountResponse)]
+    GetFeeDiscount { account: String },
+
+    #[returns(GetAdlQueueResponse)]
+    GetAdlQueue {
+        price_denom: String,
+        asset_denom: String,
+        direction: PositionDirection,
+        #[serde(default)]
+        page: PageRequest,
+    },
+
+    #[returns(GetPositionTriggersResponse)]
+    GetPositionTriggers {
+        account: String,
+        #[serde(default)]
+        page: PageRequest,
+    },
+
+    #[returns(GetOperatorsResponse)]
+    GetOperators {
+        account: String,
+        #[serde(default)]
+        page: PageRequest,
+    },
+
+    // only visible quantities are included; `depth` is the number of levels per side
+    #[returns(GetOrderBookResponse)]
+    GetOrderBook { pair: Pair, depth: Option<u32> },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct GetBalanceResponse {
+    pub amount: SignedDecimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BalanceEntry {
+    pub symbol: String,
+    pub amount: SignedDecimal,
+}
+
+pub type GetBalancesResponse = PageResponse<BalanceEntry>;
+
+pub type GetPendingWithdrawalsResponse = PageResponse<PendingWithdrawal>;
+
+// `withdrawn` is the amount of the limit's denom withdrawn so far in the response's `epoch`
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct WithdrawalLimitStatus {
+    pub limit: WithdrawalLimit,
+    pub withdrawn: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetWithdrawalLimitsResponse {
+    pub epoch: i64,
+    pub limits: PageResponse<WithdrawalLimitStatus>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CollateralEntry {
+    pub account: String,
+    pub amount: SignedDecimal,
+}
+
+pub type GetAccountsByCollateralDenomResponse = PageResponse<CollateralEntry>;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct GetPositionResponse {
+    pub long_position: SignedDecimal,
+    pub long_position_margin_debt: SignedDecimal,
+    pub long_position_last_funding_payment_epoch: i64,
+    pub long_position_pnl: SignedDecimal,
+    pub long_position_margin_mode: MarginMode,
+    pub short_position: SignedDecimal,
+    pub short_position_margin_debt: SignedDecimal,
+    pub short_position_last_funding_payment_epoch: i64,
+    pub short_position_pnl: SignedDecimal,
+    pub short_position_margin_mode: MarginMode,
+}
+
+// a pair shows up twice if the account is both long and short
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PairPosition {
+    pub pair: Pair,
+    pub position: Position,
+}
+
+pub type GetPositionsResponse = PageResponse<PairPosition>;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetPortfolioSpecsResponse {
+    pub equity: SignedDecimal,
+    pub total_position_value: SignedDecimal,
+    pub buying_power: SignedDecimal,
+    pub unrealized_pnl: SignedDecimal,
+    pub leverage: SignedDecimal,
+    pub balance: SignedDecimal,
+}
+
+pub type GetEquityHistoryResponse = PageResponse<EquitySnapshot>;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AccountPortfolioSpecs {
+    pub account: String,
+    pub specs: GetPortfolioSpecsResponse,
+}
+
+// items are in the same order as the queried accounts
+pub type GetPortfolioSpecsBatchResponse = PageResponse<AccountPortfolioSpecs>;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetInsuranceFundBalanceResponse {
+    pub balance: SignedDecimal,
+}
+
+pub type GetInsuranceFundBalancesResponse = PageResponse<DecimalCoin>;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct GetOrderResponse {
+    pub orders: Vec<Order>,
+    // parallel to `orders`: the remaining quantity shown on the book and the part held back
+    pub visible_quantities: Vec<SignedDecimal>,
+    pub hidden_quantities: Vec<SignedDecimal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct GetOrderByIdResponse {
+    pub order: Order,
+    pub status: OrderPlacementStatus,
+    pub filled_quantity: SignedDecimal,
+}
+
+pub type GetOrdersResponse = PageResponse<Order>;
+
+pub type GetExpiringOrdersResponse = PageResponse<Order>;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct GetTwapOrderProgressResponse {
+    pub progress: TwapOrderProgress,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct GetOrderByClientIdResponse {
+    pub order: Order,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct GetCumulativeFundingRateResponse {
+    pub price_diff: SignedDecimal,
+}
+
+pub type GetCumulativeFundingRatesResponse = PageResponse<CumulativeFundingRate>;
+
+// `accrued_premium` is what has built up so far in the current epoch; `predicted_rate` is the
+// payment rate that would be charged if the epoch ended now
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetFundingRateResponse {
+    pub epoch: i64,
+    pub accrued_premium: SignedDecimal,
+    pub predicted_rate: SignedDecimal,
+}
+
+// `clamp` is `None` if funding rates of the pair are unclamped
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetFundingRateClampResponse {
+    pub clamp: Option<FundingRateClamp>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetFundingSettlementConfigResponse {
+    pub config: FundingSettlementConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetFundingOverWindowResponse {
+    pub funding: SignedDecimal,
+    pub start: FundingSnapshot,
+    pub end: FundingSnapshot,
+}
+
+// open interest of a market in asset denom; `notional_value` is the total (long + short) in price denom
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetOpenInterestResponse {
+    pub long_open_interest: SignedDecimal,
+    pub short_open_interest: SignedDecimal,
+    pub notional_value: SignedDecimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct GetOrderEstimateResponse {
+    // already net of `fee_discount`
+    pub order_fee_estimate: SignedDecimal,
+    pub fee_discount: Decimal,
+    pub deposits_required: Coin,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct GetOrderEstimatesResponse {
+    // parallel to the queried orders
+    pub order_fee_estimates: Vec<SignedDecimal>,
+    pub total_order_fee_estimate: SignedDecimal,
+    pub deposits_required: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct GetConfigResponse {
+    pub admin: String,
+    pub whitelist: HashSet<Addr>,
+    pub use_whitelist: bool,
+    pub limit_order_fee: SignedDecimal,
+    pub market_order_fee: SignedDecimal,
+    pub liquidation_order_fee: SignedDecimal,
+    pub maker_fee: SignedDecimal,
+    pub taker_fee: SignedDecimal,
+    pub insurance_fund_fee_share: Decimal,
+    pub liquidation_penalty_curve: Option<LiquidationPenaltyCurve>,
+    pub default_margin_ratios: MarginRatios,
+    pub max_leverage: SignedDecimal,
+    pub min_position_notional: Decimal,
+    pub withdrawal_delay_seconds: u64,
+    pub spot_market_contract: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetLastEventSequenceResponse {
+    pub sequence: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetPendingSettlementResponse {
+    pub epoch: Option<i64>,
+    pub continuation_token: Option<String>,
+    pub remaining_entries: Vec<SettlementEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetOcoGroupResponse {
+    pub group: OcoGroup,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PlaceOcoOrdersResponse {
+    pub group: OcoGroup,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetPendingRewardsResponse {
+    pub rewards: Vec<DecimalCoin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AmendOrderResponse {
+    pub order_id: u64,
+    pub remaining_quantity: SignedDecimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct WithdrawAllResponse {
+    pub withdrawn: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CancelOrdersResponse {
+    pub unsuccessful_cancellations: Vec<UnsuccessfulCancellation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct UnsuccessfulCancellation {
+    pub id: u64,
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CancelAllOrdersResponse {
+    pub cancelled_count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetCancelOnDisconnectResponse {
+    pub switch: Option<CancelOnDisconnect>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetCancelAfterResponse {
+    pub switch: Option<CancelAfter>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetStalePricePolicyResponse {
+    pub policy: StalePricePolicy,
+}
+
+// every pending maintenance task in one response, so that a single keeper loop can service them.
+// each list is a page of the corresponding `PageRequest` in the query.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetKeeperWorkResponse {
+    pub liquidatable_accounts: PageResponse<String>,
+    pub expired_order_ids: PageResponse<u64>,
+    pub stale_funding_accounts: PageResponse<String>,
+    pub dust_position_accounts: PageResponse<String>,
+}
+
+// how a denom flows through deposits, pricing and withdrawals. `conversion_rate` converts an amount
+// in the full denom into the internal denom, `oracle_conversion_rate` does the same for oracle prices.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ResolveDenomResponse {
+    pub internal_denom: String,
+    pub full_denom: String,
+    pub conversion_rate: Decimal,
+    pub oracle_denom: Option<String>,
+    pub oracle_conversion_rate: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetAccountTierResponse {
+    pub tier: AccountTier,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetAccountTiersResponse {
+    pub risk_manager: String,
+    pub tiers: PageResponse<AccountTier>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetPositionLimitTierResponse {
+    pub tier: Option<PositionLimitTier>,
+}
+
+// `epoch` and `timestamp` (unix seconds) are those of the oracle observation the price is derived from
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetMarkPriceResponse {
+    pub mark_price: Decimal,
+    pub epoch: i64,
+    pub timestamp: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetIndexPriceResponse {
+    pub index_price: Decimal,
+    pub epoch: i64,
+    pub timestamp: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetTwapResponse {
+    pub twap: Decimal,
+    pub lookback_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LiquidatableAccount {
+    pub account: String,
+    pub margin_ratio: SignedDecimal,
+    // collateral (in base denom) missing to get back to the maintenance requirement
+    pub shortfall: SignedDecimal,
+}
+
+pub type GetLiquidatableAccountsResponse = PageResponse<LiquidatableAccount>;
+
+// computed with the same `MarginRatios` helpers the contract uses for liquidation
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetAccountHealthResponse {
+    pub margin_ratio: Decimal,
+    pub margin_ratios: MarginRatios,
+    pub maintenance_requirement: SignedDecimal,
+    pub distance_to_liquidation: SignedDecimal,
+    pub in_partial_liquidation_band: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetWhitelistResponse {
+    pub enabled: bool,
+    pub addresses: PageResponse<Addr>,
+}
+
+pub type GetTradeHistoryResponse = PageResponse<TradeRecord>;
+
+pub type GetSettlementsResponse = PageResponse<SettlementEntry>;
+
+// `None` if the position can't be liquidated by price moves alone (e.g. it is fully collateralized)
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetLiquidationPriceResponse {
+    pub liquidation_price: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetMaxOrderSizeResponse {
+    pub max_quantity: Decimal,
+    pub buying_power: SignedDecimal,
+}
+
+// version of this crate the contract was compiled against
+pub const VORTEX_COMMON_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetVersionResponse {
+    pub contract: ContractVersion,
+    pub vortex_common_version: String,
+}
+
+impl GetVersionResponse {
+    pub fn new(contract: ContractVersion) -> Self {
+        GetVersionResponse {
+            contract,
+            vortex_common_version: VORTEX_COMMON_VERSION.to_owned(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct StateEntry {
+    pub key: Binary,
+    pub value: Binary,
+}
+
+pub type ExportStateResponse = PageResponse<StateEntry>;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct OracleStatus {
+    pub pair: Pair,
+    pub last_update_epoch: i64,
+    pub is_stale: bool,
+}
+
+// one-call summary for status pages and circuit-breaker monitors. `insurance_coverage_ratio` is the
+// insurance fund balance over the total open interest notional.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetProtocolStatusResponse {
+    pub trading_paused: bool,
+    pub withdrawals_paused: bool,
+    pub liquidations_paused: bool,
+    pub reduce_only_markets: Vec<Pair>,
+    pub oracle_statuses: Vec<OracleStatus>,
+    pub insurance_coverage_ratio: Decimal,
+    pub pending_migrations: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetFeeScheduleResponse {
+    pub limit_order_fee: SignedDecimal,
+    pub market_order_fee: SignedDecimal,
+    pub liquidation_order_fee: SignedDecimal,
+    pub maker_fee: SignedDecimal,
+    pub taker_fee: SignedDecimal,
+    pub market_overrides: Vec<MarketFeeOverride>,
+    pub fee_tiers: Vec<FeeTier>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetMarketFeesResponse {
+    pub limit_order_fee: SignedDecimal,
+    pub market_order_fee: SignedDecimal,
+    pub is_override: bool,
+}
+
+// `tier` is `None` if the account doesn't qualify for any tier and pays the base maker/taker fees
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetAccountFeeTierResponse {
+    pub rolling_30d_volume: Decimal,
+    pub tier: Option<FeeTier>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetFeeDiscountResponse {
+    pub staked_amount: Decimal,
+    pub discount: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetReferralStatsResponse {
+    // the account's own referral code, if it registered one
+    pub code: Option<String>,
+    pub referrer: Option<String>,
+    pub referred_accounts: u64,
+    pub total_rewards: Vec<DecimalCoin>,
+}
+
+pub type GetAdlQueueResponse = PageResponse<AdlQueueEntry>;
+
+pub type GetPositionTriggersResponse = PageResponse<PositionTrigger>;
+
+pub type GetOperatorsResponse = PageResponse<OperatorGrant>;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetMarketResponse {
+    pub market: Market,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GetOrderBookResponse {
+    pub snapshot: OrderBookSnapshot,
+}
+
+pub type ListMarketsResponse = PageResponse<Market>;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct BulkOrderPlacementsResponse {
+    pub unsuccessful_orders: Vec<UnsuccessfulOrder>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct PlaceOrdersResponse {
+    pub successful_order_ids: Vec<u64>,
+    pub unsuccessful_orders: Vec<UnsuccessfulOrder>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct UnsuccessfulOrder {
+    pub id: u64,
+    // human readable, for display only; consumers should match on `reject_reason`
+    pub reason: String,
+    #[serde(default)]
+    pub reject_reason: OrderRejectReason,
+    #[serde(default)]
+    pub detail: Option<String>,
+}
+
+impl UnsuccessfulOrder {
+    pub fn new(id: u64, reject_reason: OrderRejectReason, detail: Option<String>) -> Self {
+        let reason = match &detail {
+            Some(detail) => format!("{}: {}", reject_reason, detail),
+            None => reject_reason.to_string(),
+        };
+        UnsuccessfulOrder {
+            id,
+            reason,
+            reject_reason,
+            detail,
+        }
+    }
+}
+
+// reasons reported in `UnsuccessfulOrder`
+pub const POST_ONLY_WOULD_CROSS: &str = "post-only order would cross the spread";
+pub const SELF_TRADE_PREVENTED: &str = "order would trade against the same account";
+pub const OPEN_INTEREST_CAP_EXCEEDED: &str = "order would exceed the market's open interest cap";
+
+// why an order was rejected, reported to the chain and indexers as a stable numeric code
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[serde(from = "i32", into = "i32")]
+pub enum OrderRejectReason {
+    #[default]
+    Unknown,
+    InsufficientMargin,
+    InvalidPair,
+    InvalidOrderData,
+    PostOnlyWouldCross,
+    SelfTradePrevented,
+    OpenInterestCapExceeded,
+    PositionLimitExceeded,
+    ReduceOnlyViolation,
+    StalePrice,
+    Paused,
+    Expired,
+}
+
+impl OrderRejectReason {
+    pub fn code(&self) -> i32 {
+        order_reject_reason_to_i32(*self)
+    }
+}
+
+impl fmt::Display for OrderRejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderRejectReason::Unknown => write!(f, "unknown reason"),
+            OrderRejectReason::InsufficientMargin => write!(f, "insufficient margin"),
+            OrderRejectReason::InvalidPair => write!(f, "invalid pair"),
+            OrderRejectReason::InvalidOrderData => write!(f, "invalid order data"),
+            OrderRejectReason::PostOnlyWouldCross => write!(f, "{}", POST_ONLY_WOULD_CROSS),
+            OrderRejectReason::SelfTradePrevented => write!(f, "{}", SELF_TRADE_PREVENTED),
+            OrderRejectReason::OpenInterestCapExceeded => {
+                write!(f, "{}", OPEN_INTEREST_CAP_EXCEEDED)
+            }
+            OrderRejectReason::PositionLimitExceeded => write!(f, "position limit exceeded"),
+            OrderRejectReason::ReduceOnlyViolation => {
+                write!(f, "reduce-only order would increase the position")
+            }
+            OrderRejectReason::StalePrice => write!(f, "oracle price is stale"),
+            OrderRejectReason::Paused => write!(f, "trading is paused"),
+            OrderRejectReason::Expired => write!(f, "order expired"),
+        }
+    }
+}
+
+impl JsonSchema for OrderRejectReason {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "OrderRejectReason".to_owned()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        i32::json_schema(gen)
+    }
+}
+
+impl From<i32> for OrderRejectReason {
+    fn from(i: i32) -> Self {
+        i32_to_order_reject_reason(i)
+    }
+}
+
+impl From<OrderRejectReason> for i32 {
+    fn from(r: OrderRejectReason) -> Self {
+        order_reject_reason_to_i32(r)
+    }
+}
+
+pub fn i32_to_order_reject_reason(i: i32) -> OrderRejectReason {
+    match i {
+        1i32 => OrderRejectReason::InsufficientMargin,
+        2i32 => OrderRejectReason::InvalidPair,
+        3i32 => OrderRejectReason::InvalidOrderData,
+        4i32 => OrderRejectReason::PostOnlyWouldCross,
+        5i32 => OrderRejectReason::SelfTradePrevented,
+        6i32 => OrderRejectReason::OpenInterestCapExceeded,
+        7i32 => OrderRejectReason::PositionLimitExceeded,
+        8i32 => OrderRejectReason::ReduceOnlyViolation,
+        9i32 => OrderRejectReason::StalePrice,
+        10i32 => OrderRejectReason::Paused,
+        11i32 => OrderRejectReason::Expired,
+        _ => OrderRejectReason::Unknown,
+    }
+}
+
+pub fn order_reject_reason_to_i32(r: OrderRejectReason) -> i32 {
+    match r {
+        OrderRejectReason::InsufficientMargin => 1i32,
+        OrderRejectReason::InvalidPair => 2i32,
+        OrderRejectReason::InvalidOrderData => 3i32,
+        OrderRejectReason::PostOnlyWouldCross => 4i32,
+        OrderRejectReason::SelfTradePrevented => 5i32,
+        OrderRejectReason::OpenInterestCapExceeded => 6i32,
+        OrderRejectReason::PositionLimitExceeded => 7i32,
+        OrderRejectReason::ReduceOnlyViolation => 8i32,
+        OrderRejectReason::StalePrice => 9i32,
+        OrderRejectReason::Paused => 10i32,
+        OrderRejectReason::Expired => 11i32,
+        OrderRejectReason::Unknown => -1i32,
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct LiquidationResponse {
+    pub successful_accounts: Vec<String>,
+    pub liquidation_orders: Vec<OrderPlacement>,
+    pub account_results: Vec<AccountLiquidationResult>,
+    // liquidation orders that could not be placed
+    #[serde(default)]
+    pub rejected_orders: Vec<UnsuccessfulOrder>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AccountLiquidationResult {
+    pub account: String,
+    pub closed_quantity: Decimal,
+    pub fully_liquidated: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AutoDeleverageResponse {
+    pub reductions: Vec<AdlReduction>,
+    // part of the shortfall left uncovered after the whole queue was processed
+    pub remaining_shortfall: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdlReduction {
+    pub account: String,
+    pub direction: PositionDirection,
+    pub quantity: Decimal,
+    pub price: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SettleDustPositionsResponse {
+    pub settled_positions: Vec<DustSettlement>,
+}
+
+// a position below `min_position_notional` that was closed at `mark_price`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DustSettlement {
+    pub account: String,
+    pub pair: Pair,
+    pub direction: PositionDirection,
+    pub quantity: Decimal,
+    pub mark_price: Decimal,
+    pub realized_pnl: SignedDecimal,
+}
+
+// returned by `NewBlock` for the accounts whose `CancelAfter` deadline passed in that block
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NewBlockResponse {
+    pub expired_heartbeats: Vec<ExpiredHeartbeat>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExpiredHeartbeat {
+    pub account: String,
+    pub deadline: u64,
+    pub cancelled_order_ids: Vec<u64>,
+}
+
+// what the contract will return for the current block's `BulkOrderPlacements` and `Liquidation`
+// sudo calls, exposed for debugging without decoding binary responses from traces
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct GetPendingSudoResultsResponse {
+    pub bulk_order_placements: BulkOrderPlacementsResponse,
+    pub liquidation: LiquidationResponse,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SettlementResponse {
+    pub processed_entries: u64,
+    // present if some entries of the epoch are still pending and need another `Settlement` call
+    pub continuation_token: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SettlementEntry {
+    pub account: String,
+    pub price_denom: String,
+    pub asset_denom: String,
+    pub quantity: Decimal,
+    pub execution_cost_or_proceed: Decimal,
+    pub expected_cost_or_proceed: Decimal,
+    pub position_direction: PositionDirection,
+    pub order_type: OrderType,
+    pub order_id: u64,
+    // which side of the fill the account was on; older chain versions don't report it
+    #[serde(default)]
+    pub liquidity_side: Option<LiquiditySide>,
+}
+
+// settlement entries of one account in one pair and direction, aggregated so that the account's
+// position is written once per epoch instead of once per fill. Entries are only netted together if
+// they also share order type and liquidity side, since those decide the fees and whether the fill
+// is accounted as a liquidation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NettedSettlement {
+    pub account: String,
+    pub price_denom: String,
+    pub asset_denom: String,
+    pub position_direction: PositionDirection,
+    pub order_type: OrderType,
+    pub liquidity_side: Option<LiquiditySide>,
+    pub quantity: Decimal,
+    pub execution_cost_or_proceed: Decimal,
+    pub expected_cost_or_proceed: Decimal,
+    pub order_ids: Vec<u64>,
+}
+
+// account, price denom, asset denom, direction, order type, liquidity side
+type NettingKey = (
+    String,
+    String,
+    String,
+    PositionDirection,
+    OrderType,
+    Option<LiquiditySide>,
+);
+
+// groups are returned in the order their first entry appears in `entries`
+pub fn net_settlements(entries: Vec<SettlementEntry>) -> Vec<NettedSettlement> {
+    let mut netted: Vec<NettedSettlement> = vec![];
+    let mut index: HashMap<NettingKey, usize> = HashMap::new();
+    for entry in entries {
+        let key = (
+            entry.account.clone(),
+            entry.price_denom.clone(),
+            entry.asset_denom.clone(),
+            entry.position_direction,
+            entry.order_type,
+            entry.liquidity_side,
+        );
+        match index.get(&key) {
+            Some(&i) => {
+                let group = &mut netted[i];
+                group.quantity += entry.quantity;
+                group.execution_cost_or_proceed += entry.execution_cost_or_proceed;
+                group.expected_cost_or_proceed += entry.expected_cost_or_proceed;
+                group.order_ids.push(entry.order_id);
+            }
+            None => {
+                index.insert(key, netted.len());
+                netted.push(NettedSettlement {
+                    account: entry.account,
+                    price_denom: entry.price_denom,
+                    asset_denom: entry.asset_denom,
+                    position_direction: entry.position_direction,
+                    order_type: entry.order_type,
+                    liquidity_side: entry.liquidity_side,
+                    quantity: entry.quantity,
+                    execution_cost_or_proceed: entry.execution_cost_or_proceed,
+                    expected_cost_or_proceed: entry.expected_cost_or_proceed,
+                    order_ids: vec![entry.order_id],
+                });
+            }
+        }
+    }
+    netted
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderPlacement {
+    pub id: u64,
+    pub status: OrderPlacementStatus,
     pub account: String,
     pub contract_address: String,
     pub price_denom: String,
@@ -325,20 +1770,222 @@ pub struct OrderPlacement {
     pub order_type: i32,
     pub position_direction: i32,
     pub data: String,
-    pub status_description: String,
+    #[serde(default)]
+    pub client_order_id: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct OrderData {
     pub leverage: Decimal,
     pub position_effect: PositionEffect,
+    #[serde(default)]
+    pub time_in_force: TimeInForce,
+    // reject the order instead of letting it take liquidity from the book
+    #[serde(default)]
+    pub post_only: bool,
+    // the order may only shrink an existing position, never flip or increase it
+    #[serde(default)]
+    pub reduce_only: bool,
+    #[serde(default)]
+    pub display_quantity: Option<Decimal>,
+    #[serde(default)]
+    pub client_order_id: Option<String>,
+    #[serde(default)]
+    pub self_trade_prevention: SelfTradePrevention,
+    #[serde(default)]
+    pub expiry_epoch: Option<i64>,
+    #[serde(default)]
+    pub twap: Option<TwapOrder>,
+    #[serde(default)]
+    pub margin_mode: MarginMode,
+}
+
+// typed content of `OrderPlacement.data`, serialized as `{"v1": {...}}`. A bare `OrderData` object
+// (the legacy encoding) is still accepted and treated as `V1`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderMetadata {
+    V1(OrderData),
+}
+
+impl OrderMetadata {
+    pub fn to_data_string(&self) -> Result<String, ContractError> {
+        serde_json_wasm::to_string(self).map_err(|err| ContractError::FailedToSerialize {
+            err_msg: err.to_string(),
+        })
+    }
+
+    pub fn into_order_data(self) -> OrderData {
+        match self {
+            OrderMetadata::V1(data) => data,
+        }
+    }
+}
+
+// serde reports missing/unknown/duplicate fields as e.g. "missing field `leverage`"
+fn offending_field(err_msg: &str) -> Option<String> {
+    if !err_msg.contains(" field `") {
+        return None;
+    }
+    err_msg.split('`').nth(1).map(|field| field.to_owned())
+}
+
+// fields of `OrderData` in declaration order; must be kept in sync with the struct
+const ORDER_DATA_FIELDS: [&str; 11] = [
+    "leverage",
+    "position_effect",
+    "time_in_force",
+    "post_only",
+    "reduce_only",
+    "display_quantity",
+    "client_order_id",
+    "self_trade_prevention",
+    "expiry_epoch",
+    "twap",
+    "margin_mode",
+];
+
+fn is_valid_order_data_field(field: &str, value: &serde_json::Value) -> bool {
+    fn parses<T: DeserializeOwned>(value: &serde_json::Value) -> bool {
+        T::deserialize(value).is_ok()
+    }
+    match field {
+        "leverage" => parses::<Decimal>(value),
+        "position_effect" => parses::<PositionEffect>(value),
+        "time_in_force" => parses::<TimeInForce>(value),
+        "post_only" | "reduce_only" => parses::<bool>(value),
+        "display_quantity" => parses::<Option<Decimal>>(value),
+        "client_order_id" => parses::<Option<String>>(value),
+        "self_trade_prevention" => parses::<SelfTradePrevention>(value),
+        "expiry_epoch" => parses::<Option<i64>>(value),
+        "twap" => parses::<Option<TwapOrder>>(value),
+        "margin_mode" => parses::<MarginMode>(value),
+        _ => true,
+    }
+}
+
+// a versioned payload is an object with a single key that is not a field of `OrderData`
+fn is_versioned_order_data(data: &str) -> bool {
+    match serde_json::from_str::<serde_json::Value>(data) {
+        Ok(serde_json::Value::Object(fields)) => {
+            fields.len() == 1
+                && fields
+                    .keys()
+                    .all(|key| !ORDER_DATA_FIELDS.contains(&key.as_str()))
+        }
+        _ => false,
+    }
+}
+
+// serde doesn't name the field for malformed values (e.g. "Invalid type"), so check the fields of
+// the payload one by one against their types and report the first one that doesn't deserialize
+fn invalid_order_data_field(data: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(data).ok()?;
+    let fields = value.get("v1").unwrap_or(&value).as_object()?;
+    ORDER_DATA_FIELDS
+        .iter()
+        .find(|field| match fields.get(**field) {
+            Some(value) => !is_valid_order_data_field(field, value),
+            None => false,
+        })
+        .map(|field| (*field).to_owned())
 }
 
 impl OrderPlacement {
+    pub fn market_id(&self) -> Result<MarketId, ContractError> {
+        MarketId::new(&self.price_denom, &self.asset_denom)
+    }
+
+    pub fn parse_metadata(&self) -> Result<OrderMetadata, ContractError> {
+        let versioned_err = match serde_json_wasm::from_str::<OrderMetadata>(&self.data) {
+            Ok(metadata) => return Ok(metadata),
+            Err(err) => err.to_string(),
+        };
+        let legacy_err = match serde_json_wasm::from_str::<OrderData>(&self.data) {
+            Ok(data) => return Ok(OrderMetadata::V1(data)),
+            Err(err) => err.to_string(),
+        };
+        // a legacy payload fails the versioned parse on its first key, so only report the
+        // versioned error if the payload actually looked versioned
+        let err_msg = if is_versioned_order_data(&self.data) {
+            versioned_err
+        } else {
+            legacy_err
+        };
+        Err(ContractError::InvalidOrderMetadata {
+            field: offending_field(&err_msg).or_else(|| invalid_order_data_field(&self.data)),
+            err_msg,
+        })
+    }
+
+    // validates the address fields so that downstream code never carries unchecked strings into
+    // storage keys or bank messages
+    pub fn validate(&self, api: &dyn Api) -> Result<ValidatedOrderPlacement, ContractError> {
+        Ok(ValidatedOrderPlacement {
+            id: self.id,
+            status: self.status,
+            account: api.addr_validate(&self.account)?,
+            contract_address: api.addr_validate(&self.contract_address)?,
+            price_denom: self.price_denom.to_owned(),
+            asset_denom: self.asset_denom.to_owned(),
+            price: self.price,
+            quantity: self.quantity,
+            order_type: self.order_type,
+            position_direction: self.position_direction,
+            data: self.data.to_owned(),
+            client_order_id: self.client_order_id.clone(),
+        })
+    }
+
     pub fn to_order(&self) -> Result<Order, ContractError> {
-        let order_data: OrderData = match serde_json_wasm::from_str(&self.data) {
-            Ok(data) => data,
-            Err(_) => return Result::Err(ContractError::InvalidOrderData {}),
+        let order_data = self.parse_metadata()?.into_order_data();
+        // `Unknown` only exists as the fallback of the i32 conversions and is never a valid input
+        if order_data.position_effect == PositionEffect::Unknown {
+            return Err(ContractError::UnknownEnumValue {
+                field: "position_effect".to_owned(),
+            });
+        }
+        if order_data.time_in_force == TimeInForce::Unknown {
+            return Err(ContractError::UnknownEnumValue {
+                field: "time_in_force".to_owned(),
+            });
+        }
+        // GTD orders expire at the end of their time-in-force window; an explicit expiry has to
+        // agree with it so that the order has a single expiry
+        let expiry_epoch = match (
+            order_data.expiry_epoch,
+            order_data.time_in_force.expiry_epoch(),
+        ) {
+            (Some(expiry_epoch), Some(tif_expiry_epoch)) if expiry_epoch != tif_expiry_epoch => {
+                return Err(ContractError::InvalidOrderMetadata {
+                    field: Some("expiry_epoch".to_owned()),
+                    err_msg: format!(
+                        "expiry epoch {} conflicts with the GTD expiry epoch {}",
+                        expiry_epoch, tif_expiry_epoch
+                    ),
+                });
+            }
+            (expiry_epoch, tif_expiry_epoch) => expiry_epoch.or(tif_expiry_epoch),
+        };
+        let order_type = OrderType::try_from(self.order_type)?;
+        // fill-or-kill market orders can only be FOK; the default GTC is read as "not specified"
+        let time_in_force = match (order_type, order_data.time_in_force) {
+            (OrderType::Fokmarket | OrderType::Fokmarketbyvalue, TimeInForce::Gtc) => {
+                TimeInForce::Fok
+            }
+            (OrderType::Fokmarket | OrderType::Fokmarketbyvalue, TimeInForce::Fok) => {
+                TimeInForce::Fok
+            }
+            (OrderType::Fokmarket | OrderType::Fokmarketbyvalue, time_in_force) => {
+                return Err(ContractError::InvalidOrderMetadata {
+                    field: Some("time_in_force".to_owned()),
+                    err_msg: format!(
+                        "{} orders can't have time in force {}",
+                        order_type, time_in_force
+                    ),
+                });
+            }
+            (_, time_in_force) => time_in_force,
         };
         let order = Order {
             id: self.id,
@@ -348,15 +1995,61 @@ impl OrderPlacement {
             price: SignedDecimal::new(self.price),
             quantity: SignedDecimal::new(self.quantity),
             remaining_quantity: SignedDecimal::new(self.quantity),
-            direction: i32_to_direction(self.position_direction),
-            order_type: i32_to_order_type(self.order_type),
+            direction: PositionDirection::try_from(self.position_direction)?,
+            order_type,
             effect: order_data.position_effect,
             leverage: SignedDecimal::new(order_data.leverage),
+            time_in_force,
+            post_only: order_data.post_only,
+            reduce_only: order_data.reduce_only,
+            display_quantity: order_data.display_quantity.map(SignedDecimal::new),
+            // an id set on the placement itself takes precedence over the one in the order data
+            client_order_id: self.client_order_id.clone().or(order_data.client_order_id),
+            self_trade_prevention: order_data.self_trade_prevention,
+            expiry_epoch,
+            twap: order_data.twap,
+            margin_mode: order_data.margin_mode,
         };
         Result::Ok(order)
     }
 }
 
+// `OrderPlacement` whose addresses have been validated, see `OrderPlacement::validate`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ValidatedOrderPlacement {
+    pub id: u64,
+    pub status: OrderPlacementStatus,
+    pub account: Addr,
+    pub contract_address: Addr,
+    pub price_denom: String,
+    pub asset_denom: String,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub order_type: i32,
+    pub position_direction: i32,
+    pub data: String,
+    pub client_order_id: Option<String>,
+}
+
+impl From<ValidatedOrderPlacement> for OrderPlacement {
+    fn from(placement: ValidatedOrderPlacement) -> Self {
+        OrderPlacement {
+            id: placement.id,
+            status: placement.status,
+            account: placement.account.into_string(),
+            contract_address: placement.contract_address.into_string(),
+            price_denom: placement.price_denom,
+            asset_denom: placement.asset_denom,
+            price: placement.price,
+            quantity: placement.quantity,
+            order_type: placement.order_type,
+            position_direction: placement.position_direction,
+            data: placement.data,
+            client_order_id: placement.client_order_id,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct DepositInfo {
     pub account: String,
@@ -368,6 +2061,12 @@ pub struct DepositInfo {
 pub struct LiquidationRequest {
     pub requestor: String,
     pub account: String,
+    // for accounts in the partial liquidation band: close at most this quantity, or only as much as
+    // needed to bring the account back to `target_margin_ratio`. Both unset means full liquidation.
+    #[serde(default)]
+    pub max_close_quantity: Option<Decimal>,
+    #[serde(default)]
+    pub target_margin_ratio: Option<Decimal>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -380,7 +2079,7 @@ pub struct ContractOrderResult {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct OrderPlacementResult {
     pub order_id: u64,
-    pub status_code: i32,
+    pub status_code: OrderPlacementStatus,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -390,4 +2089,28 @@ pub struct OrderExecutionResult {
     pub executed_quantity: Decimal,
     pub total_notional: Decimal,
     pub position_direction: String,
+    // individual fills making up the execution; empty for chain versions that only report the
+    // averaged `execution_price`
+    #[serde(default)]
+    pub fills: Vec<Fill>,
+}
+
+impl OrderExecutionResult {
+    pub fn direction(&self) -> Result<PositionDirection, ContractError> {
+        self.position_direction.parse()
+    }
+
+    pub fn maker_quantity(&self) -> Decimal {
+        self.fills
+            .iter()
+            .filter(|fill| fill.maker)
+            .fold(Decimal::zero(), |acc, fill| acc + fill.quantity)
+    }
+
+    pub fn taker_quantity(&self) -> Decimal {
+        self.fills
+            .iter()
+            .filter(|fill| !fill.maker)
+            .fold(Decimal::zero(), |acc, fill| acc + fill.quantity)
+    }
 }
@@ -0,0 +1,365 @@
+// protobuf encodings of the bulk sudo payloads, for chains that send them as binary instead of JSON.
+// Decimals are encoded as strings, the same way the JSON payloads carry them. Each payload converts
+// into the corresponding `SudoMsg` so that contracts handle a single representation.
+// The field tags and message names below are this crate's own layout. They were not generated from
+// the chain's .proto files and have not been checked against bytes from any sei-chain version, so
+// they are not known to match what the chain sends. `tests/proto_layout.rs` only guards the layout
+// against accidental changes; don't enable this feature in a deployed contract until a capture from
+// the chain has been added as described in `tests/fixtures/README.md`.
+use std::str::FromStr;
+
+use cosmwasm_std::Decimal;
+use prost::Message;
+
+use crate::{
+    error::ContractError,
+    msg::{
+        ContractOrderResult, DecimalCoin, DepositInfo, OrderExecutionResult, OrderPlacement,
+        OrderPlacementResult, SettlementEntry, SudoMsg,
+    },
+    types::{Fill, LiquiditySide, OrderType, Pair, PositionDirection},
+};
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoOrderPlacement {
+    #[prost(uint64, tag = "1")]
+    pub id: u64,
+    #[prost(int32, tag = "2")]
+    pub status: i32,
+    #[prost(string, tag = "3")]
+    pub account: String,
+    #[prost(string, tag = "4")]
+    pub contract_address: String,
+    #[prost(string, tag = "5")]
+    pub price_denom: String,
+    #[prost(string, tag = "6")]
+    pub asset_denom: String,
+    #[prost(string, tag = "7")]
+    pub price: String,
+    #[prost(string, tag = "8")]
+    pub quantity: String,
+    #[prost(int32, tag = "9")]
+    pub order_type: i32,
+    #[prost(int32, tag = "10")]
+    pub position_direction: i32,
+    #[prost(string, tag = "11")]
+    pub data: String,
+    #[prost(string, optional, tag = "12")]
+    pub client_order_id: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoDepositInfo {
+    #[prost(string, tag = "1")]
+    pub account: String,
+    #[prost(string, tag = "2")]
+    pub denom: String,
+    #[prost(string, tag = "3")]
+    pub amount: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct BulkOrderPlacementsPayload {
+    #[prost(message, repeated, tag = "1")]
+    pub orders: Vec<ProtoOrderPlacement>,
+    #[prost(message, repeated, tag = "2")]
+    pub deposits: Vec<ProtoDepositInfo>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoSettlementEntry {
+    #[prost(string, tag = "1")]
+    pub account: String,
+    #[prost(string, tag = "2")]
+    pub price_denom: String,
+    #[prost(string, tag = "3")]
+    pub asset_denom: String,
+    #[prost(string, tag = "4")]
+    pub quantity: String,
+    #[prost(string, tag = "5")]
+    pub execution_cost_or_proceed: String,
+    #[prost(string, tag = "6")]
+    pub expected_cost_or_proceed: String,
+    #[prost(int32, tag = "7")]
+    pub position_direction: i32,
+    #[prost(int32, tag = "8")]
+    pub order_type: i32,
+    #[prost(uint64, tag = "9")]
+    pub order_id: u64,
+    // 0 is maker, 1 is taker
+    #[prost(int32, optional, tag = "10")]
+    pub liquidity_side: Option<i32>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct SettlementPayload {
+    #[prost(int64, tag = "1")]
+    pub epoch: i64,
+    #[prost(message, repeated, tag = "2")]
+    pub entries: Vec<ProtoSettlementEntry>,
+    #[prost(string, optional, tag = "3")]
+    pub continuation_token: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoOrderPlacementResult {
+    #[prost(uint64, tag = "1")]
+    pub order_id: u64,
+    #[prost(int32, tag = "2")]
+    pub status_code: i32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoOrderExecutionResult {
+    #[prost(uint64, tag = "1")]
+    pub order_id: u64,
+    #[prost(string, tag = "2")]
+    pub execution_price: String,
+    #[prost(string, tag = "3")]
+    pub executed_quantity: String,
+    #[prost(string, tag = "4")]
+    pub total_notional: String,
+    #[prost(string, tag = "5")]
+    pub position_direction: String,
+    #[prost(message, repeated, tag = "6")]
+    pub fills: Vec<ProtoFill>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoFill {
+    #[prost(uint64, tag = "1")]
+    pub order_id: u64,
+    #[prost(string, tag = "2")]
+    pub account: String,
+    #[prost(string, tag = "3")]
+    pub price_denom: String,
+    #[prost(string, tag = "4")]
+    pub asset_denom: String,
+    #[prost(int32, tag = "5")]
+    pub direction: i32,
+    #[prost(string, tag = "6")]
+    pub price: String,
+    #[prost(string, tag = "7")]
+    pub quantity: String,
+    #[prost(string, tag = "8")]
+    pub fee_denom: String,
+    #[prost(string, tag = "9")]
+    pub fee_amount: String,
+    #[prost(bool, tag = "10")]
+    pub maker: bool,
+    #[prost(int64, tag = "11")]
+    pub epoch: i64,
+    #[prost(uint64, tag = "12")]
+    pub timestamp: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoContractOrderResult {
+    #[prost(string, tag = "1")]
+    pub contract_address: String,
+    #[prost(message, repeated, tag = "2")]
+    pub order_placement_results: Vec<ProtoOrderPlacementResult>,
+    #[prost(message, repeated, tag = "3")]
+    pub order_execution_results: Vec<ProtoOrderExecutionResult>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct FinalizeBlockPayload {
+    #[prost(message, repeated, tag = "1")]
+    pub contract_order_results: Vec<ProtoContractOrderResult>,
+}
+
+fn parse_decimal(value: &str) -> Result<Decimal, ContractError> {
+    Ok(Decimal::from_str(value)?)
+}
+
+fn decode<T: Message + Default>(bytes: &[u8]) -> Result<T, ContractError> {
+    T::decode(bytes).map_err(|err| ContractError::InvalidSudoMsg {
+        err_msg: err.to_string(),
+    })
+}
+
+impl TryFrom<ProtoOrderPlacement> for OrderPlacement {
+    type Error = ContractError;
+
+    fn try_from(order: ProtoOrderPlacement) -> Result<Self, Self::Error> {
+        Ok(OrderPlacement {
+            id: order.id,
+            status: order.status.into(),
+            account: order.account,
+            contract_address: order.contract_address,
+            price_denom: order.price_denom,
+            asset_denom: order.asset_denom,
+            price: parse_decimal(&order.price)?,
+            quantity: parse_decimal(&order.quantity)?,
+            order_type: order.order_type,
+            position_direction: order.position_direction,
+            data: order.data,
+            client_order_id: order.client_order_id,
+        })
+    }
+}
+
+impl TryFrom<ProtoDepositInfo> for DepositInfo {
+    type Error = ContractError;
+
+    fn try_from(deposit: ProtoDepositInfo) -> Result<Self, Self::Error> {
+        Ok(DepositInfo {
+            account: deposit.account,
+            denom: deposit.denom,
+            amount: parse_decimal(&deposit.amount)?,
+        })
+    }
+}
+
+impl TryFrom<BulkOrderPlacementsPayload> for SudoMsg {
+    type Error = ContractError;
+
+    fn try_from(payload: BulkOrderPlacementsPayload) -> Result<Self, Self::Error> {
+        Ok(SudoMsg::BulkOrderPlacements {
+            orders: payload
+                .orders
+                .into_iter()
+                .map(OrderPlacement::try_from)
+                .collect::<Result<_, _>>()?,
+            deposits: payload
+                .deposits
+                .into_iter()
+                .map(DepositInfo::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<ProtoSettlementEntry> for SettlementEntry {
+    type Error = ContractError;
+
+    fn try_from(entry: ProtoSettlementEntry) -> Result<Self, Self::Error> {
+        let liquidity_side = match entry.liquidity_side {
+            None => None,
+            Some(0) => Some(LiquiditySide::Maker),
+            Some(1) => Some(LiquiditySide::Taker),
+            Some(other) => {
+                return Err(ContractError::InvalidSudoMsg {
+                    err_msg: format!("unknown liquidity side {}", other),
+                })
+            }
+        };
+        Ok(SettlementEntry {
+            account: entry.account,
+            price_denom: entry.price_denom,
+            asset_denom: entry.asset_denom,
+            quantity: parse_decimal(&entry.quantity)?,
+            execution_cost_or_proceed: parse_decimal(&entry.execution_cost_or_proceed)?,
+            expected_cost_or_proceed: parse_decimal(&entry.expected_cost_or_proceed)?,
+            position_direction: PositionDirection::try_from(entry.position_direction)?,
+            order_type: OrderType::try_from(entry.order_type)?,
+            order_id: entry.order_id,
+            liquidity_side,
+        })
+    }
+}
+
+impl TryFrom<SettlementPayload> for SudoMsg {
+    type Error = ContractError;
+
+    fn try_from(payload: SettlementPayload) -> Result<Self, Self::Error> {
+        Ok(SudoMsg::Settlement {
+            epoch: payload.epoch,
+            entries: payload
+                .entries
+                .into_iter()
+                .map(SettlementEntry::try_from)
+                .collect::<Result<_, _>>()?,
+            continuation_token: payload.continuation_token,
+        })
+    }
+}
+
+impl TryFrom<ProtoFill> for Fill {
+    type Error = ContractError;
+
+    fn try_from(fill: ProtoFill) -> Result<Self, Self::Error> {
+        Ok(Fill {
+            order_id: fill.order_id,
+            account: fill.account,
+            pair: Pair {
+                price_denom: fill.price_denom,
+                asset_denom: fill.asset_denom,
+            },
+            direction: PositionDirection::try_from(fill.direction)?,
+            price: parse_decimal(&fill.price)?,
+            quantity: parse_decimal(&fill.quantity)?,
+            fee: DecimalCoin {
+                denom: fill.fee_denom,
+                amount: parse_decimal(&fill.fee_amount)?,
+            },
+            maker: fill.maker,
+            epoch: fill.epoch,
+            timestamp: fill.timestamp,
+        })
+    }
+}
+
+impl TryFrom<ProtoContractOrderResult> for ContractOrderResult {
+    type Error = ContractError;
+
+    fn try_from(result: ProtoContractOrderResult) -> Result<Self, Self::Error> {
+        let order_execution_results = result
+            .order_execution_results
+            .into_iter()
+            .map(|execution| {
+                Ok(OrderExecutionResult {
+                    order_id: execution.order_id,
+                    execution_price: parse_decimal(&execution.execution_price)?,
+                    executed_quantity: parse_decimal(&execution.executed_quantity)?,
+                    total_notional: parse_decimal(&execution.total_notional)?,
+                    position_direction: execution.position_direction,
+                    fills: execution
+                        .fills
+                        .into_iter()
+                        .map(Fill::try_from)
+                        .collect::<Result<_, _>>()?,
+                })
+            })
+            .collect::<Result<_, ContractError>>()?;
+        Ok(ContractOrderResult {
+            contract_address: result.contract_address,
+            order_placement_results: result
+                .order_placement_results
+                .into_iter()
+                .map(|placement| OrderPlacementResult {
+                    order_id: placement.order_id,
+                    status_code: placement.status_code.into(),
+                })
+                .collect(),
+            order_execution_results,
+        })
+    }
+}
+
+impl TryFrom<FinalizeBlockPayload> for SudoMsg {
+    type Error = ContractError;
+
+    fn try_from(payload: FinalizeBlockPayload) -> Result<Self, Self::Error> {
+        Ok(SudoMsg::FinalizeBlock {
+            contract_order_results: payload
+                .contract_order_results
+                .into_iter()
+                .map(ContractOrderResult::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+pub fn decode_bulk_order_placements(bytes: &[u8]) -> Result<SudoMsg, ContractError> {
+    decode::<BulkOrderPlacementsPayload>(bytes)?.try_into()
+}
+
+pub fn decode_settlement(bytes: &[u8]) -> Result<SudoMsg, ContractError> {
+    decode::<SettlementPayload>(bytes)?.try_into()
+}
+
+pub fn decode_finalize_block(bytes: &[u8]) -> Result<SudoMsg, ContractError> {
+    decode::<FinalizeBlockPayload>(bytes)?.try_into()
+}
@@ -0,0 +1,13 @@
+// The entry point messages of the Vortex contract, in the shape `cosmwasm_schema::write_api!` expects.
+// Consumers can generate the JSON schema (and typed clients from it) with e.g.
+//
+//     write_api! {
+//         instantiate: InstantiateMsg,
+//         execute: ExecuteMsg,
+//         query: QueryMsg,
+//         migrate: MigrateMsg,
+//         sudo: SudoMsg,
+//     }
+//
+// or run `cargo run --example schema` in this crate.
+pub use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
@@ -1,10 +1,17 @@
+use std::collections::HashMap;
 use std::fmt;
-use std::io::Write;
+use std::ops::BitOr;
+use std::str::FromStr;
 
+use crate::error::ContractError;
+use crate::msg::DecimalCoin;
 use crate::utils::SignedDecimal;
-use cosmwasm_std::{Decimal, StdError};
-use cw_storage_plus::{Key, KeyDeserialize, Prefixer, PrimaryKey};
+use cosmwasm_std::{Api, Binary, Coin, Decimal, StdError, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Key, KeyDeserialize, Map, Prefixer, PrimaryKey};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
 use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -20,6 +27,88 @@ pub struct Order {
     pub effect: PositionEffect,
     pub leverage: SignedDecimal,
     pub order_type: OrderType,
+    // the fields below were added after the first release, so orders serialized before then
+    // deserialize with their defaults
+    #[serde(default)]
+    pub time_in_force: TimeInForce,
+    #[serde(default)]
+    pub post_only: bool,
+    #[serde(default)]
+    pub reduce_only: bool,
+    // for iceberg orders, the slice of `remaining_quantity` that is exposed to the book at a time
+    #[serde(default)]
+    pub display_quantity: Option<SignedDecimal>,
+    #[serde(default)]
+    pub client_order_id: Option<String>,
+    #[serde(default)]
+    pub self_trade_prevention: SelfTradePrevention,
+    // last epoch in which the order is live; it is expired by the chain afterwards. For GTD orders
+    // this is always the expiry epoch of `time_in_force`
+    #[serde(default)]
+    pub expiry_epoch: Option<i64>,
+    #[serde(default)]
+    pub twap: Option<TwapOrder>,
+    #[serde(default)]
+    pub margin_mode: MarginMode,
+}
+
+impl Order {
+    pub fn visible_remaining_quantity(&self) -> SignedDecimal {
+        match self.display_quantity {
+            Some(display_quantity) if display_quantity < self.remaining_quantity => {
+                display_quantity
+            }
+            _ => self.remaining_quantity,
+        }
+    }
+
+    pub fn hidden_remaining_quantity(&self) -> SignedDecimal {
+        self.remaining_quantity - self.visible_remaining_quantity()
+    }
+
+    pub fn is_expired(&self, current_epoch: i64) -> bool {
+        match self.expiry_epoch {
+            Some(expiry_epoch) => current_epoch > expiry_epoch,
+            None => false,
+        }
+    }
+
+    pub fn market_id(&self) -> Result<MarketId, ContractError> {
+        MarketId::new(&self.price_denom, &self.asset_denom)
+    }
+}
+
+// orders placed together where the execution of any one of them cancels all the others
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OcoGroup {
+    pub group_id: u64,
+    pub order_ids: Vec<u64>,
+}
+
+// an order that is executed in `num_slices` equal slices, one every `interval_epochs` epochs
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TwapOrder {
+    pub total_quantity: Decimal,
+    pub num_slices: u32,
+    pub interval_epochs: u64,
+}
+
+impl TwapOrder {
+    pub fn slice_quantity(&self) -> Decimal {
+        if self.num_slices == 0 {
+            return Decimal::zero();
+        }
+        self.total_quantity * Decimal::from_ratio(1u128, self.num_slices)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TwapOrderProgress {
+    pub order_id: u64,
+    pub executed_slices: u32,
+    pub executed_quantity: Decimal,
+    // `None` once all slices have been executed
+    pub next_slice_epoch: Option<i64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -28,6 +117,161 @@ pub struct FundingPaymentRate {
     pub epoch: i64,
 }
 
+// cumulative funding of a pair at the end of an epoch. `cumulative_rate` is the prefix sum of every
+// per-epoch `price_diff` up to and including `epoch`, so funding over any lookback window is the
+// difference of two snapshots rather than an iteration over every epoch in the window.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FundingSnapshot {
+    pub epoch: i64,
+    pub cumulative_rate: SignedDecimal,
+}
+
+impl FundingSnapshot {
+    pub fn genesis(epoch: i64) -> Self {
+        FundingSnapshot {
+            epoch,
+            cumulative_rate: SignedDecimal::zero(),
+        }
+    }
+
+    // the snapshot after applying the funding rate of the next epoch
+    pub fn accumulate(&self, rate: &FundingPaymentRate) -> Self {
+        FundingSnapshot {
+            epoch: rate.epoch,
+            cumulative_rate: self.cumulative_rate + rate.price_diff,
+        }
+    }
+
+    // funding accrued after `start` up to and including this snapshot's epoch
+    pub fn funding_since(&self, start: &FundingSnapshot) -> SignedDecimal {
+        self.cumulative_rate - start.cumulative_rate
+    }
+}
+
+// storage key of a `FundingSnapshot`. Prefixing with a `Pair` ranges over that pair's snapshots in
+// epoch order, so the latest snapshot at or before an epoch is a single reverse range lookup, see
+// `latest_funding_snapshot`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FundingSnapshotKey {
+    pub pair: Pair,
+    pub epoch: i64,
+}
+
+impl<'a> PrimaryKey<'a> for FundingSnapshotKey {
+    type Prefix = Pair;
+
+    type SubPrefix = ();
+
+    type Suffix = i64;
+
+    type SuperSuffix = Self;
+
+    fn key(&self) -> Vec<Key<'_>> {
+        let mut keys = self.pair.key();
+        keys.extend(self.epoch.key());
+        keys
+    }
+}
+
+impl KeyDeserialize for FundingSnapshotKey {
+    type Output = FundingSnapshotKey;
+
+    fn from_vec(value: Vec<u8>) -> cosmwasm_std::StdResult<Self::Output> {
+        let (price_denom, asset_denom, epoch) = <(String, String, i64)>::from_vec(value)?;
+        Ok(FundingSnapshotKey {
+            pair: Pair {
+                price_denom,
+                asset_denom,
+            },
+            epoch,
+        })
+    }
+}
+
+// the latest snapshot of `pair` at or before `epoch`, i.e. the cumulative funding as of that epoch
+// even if no funding was recorded in it. `None` if the pair has no snapshot that early.
+pub fn latest_funding_snapshot(
+    storage: &dyn Storage,
+    snapshots: &Map<FundingSnapshotKey, FundingSnapshot>,
+    pair: &Pair,
+    epoch: i64,
+) -> StdResult<Option<FundingSnapshot>> {
+    snapshots
+        .prefix(pair.clone())
+        .range(
+            storage,
+            None,
+            Some(Bound::inclusive(epoch)),
+            cosmwasm_std::Order::Descending,
+        )
+        .next()
+        .transpose()
+        .map(|entry| entry.map(|(_, snapshot)| snapshot))
+}
+
+// per-side cumulative funding indices of a pair as of the end of `epoch`. Both sides are always
+// advanced together, so the two indices are for the same epoch. A position owes the difference
+// between its side's index and its `last_paid_funding_payment_rate`, times its quantity.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CumulativeFundingRate {
+    pub pair: Pair,
+    pub long_index: SignedDecimal,
+    pub short_index: SignedDecimal,
+    pub epoch: i64,
+}
+
+impl CumulativeFundingRate {
+    pub fn genesis(pair: Pair, epoch: i64) -> Self {
+        CumulativeFundingRate {
+            pair,
+            long_index: SignedDecimal::zero(),
+            short_index: SignedDecimal::zero(),
+            epoch,
+        }
+    }
+
+    // the indices after applying the per-side funding rates of `epoch`
+    pub fn accumulate(
+        &self,
+        epoch: i64,
+        long_price_diff: SignedDecimal,
+        short_price_diff: SignedDecimal,
+    ) -> Self {
+        CumulativeFundingRate {
+            pair: self.pair.clone(),
+            long_index: self.long_index + long_price_diff,
+            short_index: self.short_index + short_price_diff,
+            epoch,
+        }
+    }
+
+    // the index of one side as a `FundingSnapshot`, e.g. to compute funding over a window
+    // `None` for `PositionDirection::Unknown`, which has no funding index
+    pub fn snapshot(&self, direction: PositionDirection) -> Option<FundingSnapshot> {
+        self.index(direction)
+            .map(|cumulative_rate| FundingSnapshot {
+                epoch: self.epoch,
+                cumulative_rate,
+            })
+    }
+
+    pub fn index(&self, direction: PositionDirection) -> Option<SignedDecimal> {
+        match direction {
+            PositionDirection::Long => Some(self.long_index),
+            PositionDirection::Short => Some(self.short_index),
+            PositionDirection::Unknown => None,
+        }
+    }
+
+    // positive if the position owes funding, negative if it is owed funding
+    pub fn pending_funding(&self, position: &Position) -> Result<SignedDecimal, ContractError> {
+        let index = self
+            .index(position.direction)
+            .ok_or(ContractError::InvalidPositionDirection {})?;
+        Ok((index - position.last_paid_funding_payment_rate) * position.quantity)
+    }
+}
+
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, JsonSchema, Eq, Hash)]
 pub enum PositionDirection {
     Unknown,
@@ -45,6 +289,19 @@ impl fmt::Display for PositionDirection {
     }
 }
 
+// case-insensitive, so "long", "Long" and "LONG" all parse
+impl FromStr for PositionDirection {
+    type Err = ContractError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "long" => Ok(PositionDirection::Long),
+            "short" => Ok(PositionDirection::Short),
+            _ => Err(ContractError::InvalidPositionDirection {}),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, JsonSchema, Eq, Hash)]
 pub enum PositionEffect {
     Unknown,
@@ -62,6 +319,18 @@ impl fmt::Display for PositionEffect {
     }
 }
 
+impl FromStr for PositionEffect {
+    type Err = ContractError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "open" => Ok(PositionEffect::Open),
+            "close" => Ok(PositionEffect::Close),
+            _ => Err(ContractError::InvalidPositionEffect {}),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, JsonSchema, Eq, Hash)]
 pub enum OrderType {
     Unknown,
@@ -85,6 +354,198 @@ impl fmt::Display for OrderType {
     }
 }
 
+impl FromStr for OrderType {
+    type Err = ContractError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "limit" => Ok(OrderType::Limit),
+            "market" => Ok(OrderType::Market),
+            "liquidation" => Ok(OrderType::Liquidation),
+            "fokmarket" => Ok(OrderType::Fokmarket),
+            "fokmarketbyvalue" => Ok(OrderType::Fokmarketbyvalue),
+            _ => Err(ContractError::InvalidOrderType {}),
+        }
+    }
+}
+
+// how long an order stays active before it is cancelled. This is orthogonal to `OrderType` so that
+// e.g. an IOC limit order can be expressed; `Gtd` carries the last epoch in which the order is live.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, JsonSchema, Eq, Hash)]
+pub enum TimeInForce {
+    Unknown,
+    #[default]
+    Gtc,
+    Ioc,
+    Fok,
+    Gtd {
+        expiry_epoch: i64,
+    },
+}
+
+impl TimeInForce {
+    pub fn expiry_epoch(&self) -> Option<i64> {
+        match self {
+            TimeInForce::Gtd { expiry_epoch } => Some(*expiry_epoch),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TimeInForce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeInForce::Unknown => write!(f, "Unknown"),
+            TimeInForce::Gtc => write!(f, "Gtc"),
+            TimeInForce::Ioc => write!(f, "Ioc"),
+            TimeInForce::Fok => write!(f, "Fok"),
+            TimeInForce::Gtd { expiry_epoch } => write!(f, "Gtd({})", expiry_epoch),
+        }
+    }
+}
+
+// the i32 wire format has no room for the GTD expiry, so it has to be supplied separately
+pub fn i32_to_time_in_force(i: i32, expiry_epoch: i64) -> TimeInForce {
+    match i {
+        0i32 => TimeInForce::Gtc,
+        1i32 => TimeInForce::Ioc,
+        2i32 => TimeInForce::Fok,
+        3i32 => TimeInForce::Gtd { expiry_epoch },
+        _ => TimeInForce::Unknown,
+    }
+}
+
+pub fn time_in_force_to_i32(t: TimeInForce) -> i32 {
+    match t {
+        TimeInForce::Gtc => 0i32,
+        TimeInForce::Ioc => 1i32,
+        TimeInForce::Fok => 2i32,
+        TimeInForce::Gtd { .. } => 3i32,
+        TimeInForce::Unknown => -1i32,
+    }
+}
+
+// what happens when an incoming order would match a resting order of the same account
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, JsonSchema, Eq, Hash)]
+pub enum SelfTradePrevention {
+    #[default]
+    Allow,
+    CancelTaker,
+    CancelMaker,
+    CancelBoth,
+}
+
+impl fmt::Display for SelfTradePrevention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SelfTradePrevention::Allow => write!(f, "Allow"),
+            SelfTradePrevention::CancelTaker => write!(f, "CancelTaker"),
+            SelfTradePrevention::CancelMaker => write!(f, "CancelMaker"),
+            SelfTradePrevention::CancelBoth => write!(f, "CancelBoth"),
+        }
+    }
+}
+
+// outcome of placing an order on the chain's order book. It travels as a plain i32 on the wire, which is
+// why serde goes through the i32 conversions below. `Unknown` keeps the code of a status this crate
+// doesn't know yet, so that it is written back unchanged.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(from = "i32", into = "i32")]
+pub enum OrderPlacementStatus {
+    Unknown(i32),
+    Placed,
+    FailedToPlace,
+    Cancelled,
+    Fulfilled,
+    FailedInsufficientMargin,
+    FailedInvalidPair,
+    FailedInvalidOrderData,
+}
+
+impl fmt::Display for OrderPlacementStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderPlacementStatus::Unknown(code) => write!(f, "Unknown({})", code),
+            OrderPlacementStatus::Placed => write!(f, "Placed"),
+            OrderPlacementStatus::FailedToPlace => write!(f, "FailedToPlace"),
+            OrderPlacementStatus::Cancelled => write!(f, "Cancelled"),
+            OrderPlacementStatus::Fulfilled => write!(f, "Fulfilled"),
+            OrderPlacementStatus::FailedInsufficientMargin => write!(f, "FailedInsufficientMargin"),
+            OrderPlacementStatus::FailedInvalidPair => write!(f, "FailedInvalidPair"),
+            OrderPlacementStatus::FailedInvalidOrderData => write!(f, "FailedInvalidOrderData"),
+        }
+    }
+}
+
+impl JsonSchema for OrderPlacementStatus {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "OrderPlacementStatus".to_owned()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        i32::json_schema(gen)
+    }
+}
+
+impl From<i32> for OrderPlacementStatus {
+    fn from(i: i32) -> Self {
+        i32_to_order_placement_status(i)
+    }
+}
+
+impl From<OrderPlacementStatus> for i32 {
+    fn from(s: OrderPlacementStatus) -> Self {
+        order_placement_status_to_i32(s)
+    }
+}
+
+pub fn i32_to_order_placement_status(i: i32) -> OrderPlacementStatus {
+    match i {
+        0i32 => OrderPlacementStatus::Placed,
+        1i32 => OrderPlacementStatus::FailedToPlace,
+        2i32 => OrderPlacementStatus::Cancelled,
+        3i32 => OrderPlacementStatus::Fulfilled,
+        4i32 => OrderPlacementStatus::FailedInsufficientMargin,
+        5i32 => OrderPlacementStatus::FailedInvalidPair,
+        6i32 => OrderPlacementStatus::FailedInvalidOrderData,
+        code => OrderPlacementStatus::Unknown(code),
+    }
+}
+
+pub fn order_placement_status_to_i32(s: OrderPlacementStatus) -> i32 {
+    match s {
+        OrderPlacementStatus::Placed => 0i32,
+        OrderPlacementStatus::FailedToPlace => 1i32,
+        OrderPlacementStatus::Cancelled => 2i32,
+        OrderPlacementStatus::Fulfilled => 3i32,
+        OrderPlacementStatus::FailedInsufficientMargin => 4i32,
+        OrderPlacementStatus::FailedInvalidPair => 5i32,
+        OrderPlacementStatus::FailedInvalidOrderData => 6i32,
+        OrderPlacementStatus::Unknown(code) => code,
+    }
+}
+
+// rejects values the chain doesn't define instead of mapping them to `Unknown`
+impl TryFrom<i32> for OrderType {
+    type Error = ContractError;
+
+    #[allow(deprecated)]
+    fn try_from(i: i32) -> Result<Self, Self::Error> {
+        match i32_to_order_type(i) {
+            OrderType::Unknown => Err(ContractError::InvalidEnumValue {
+                field: "order_type".to_owned(),
+                value: i,
+            }),
+            order_type => Ok(order_type),
+        }
+    }
+}
+
+#[deprecated(note = "use `OrderType::try_from`, which rejects unknown values")]
 pub fn i32_to_order_type(i: i32) -> OrderType {
     match i {
         0i32 => OrderType::Limit,
@@ -107,6 +568,22 @@ pub fn order_type_to_i32(o: OrderType) -> i32 {
     }
 }
 
+impl TryFrom<i32> for PositionDirection {
+    type Error = ContractError;
+
+    #[allow(deprecated)]
+    fn try_from(i: i32) -> Result<Self, Self::Error> {
+        match i32_to_direction(i) {
+            PositionDirection::Unknown => Err(ContractError::InvalidEnumValue {
+                field: "position_direction".to_owned(),
+                value: i,
+            }),
+            direction => Ok(direction),
+        }
+    }
+}
+
+#[deprecated(note = "use `PositionDirection::try_from`, which rejects unknown values")]
 pub fn i32_to_direction(i: i32) -> PositionDirection {
     match i {
         0i32 => PositionDirection::Long,
@@ -132,69 +609,169 @@ pub struct Pair {
 }
 
 impl Pair {
-    fn to_bytes(&self) -> [u8; 16] {
+    // encoding used for storage keys before they became length-prefixed. Denoms longer than 8 bytes
+    // are truncated, so distinct pairs like two `factory/...` denoms can share a legacy key.
+    pub fn legacy_key_bytes(&self) -> [u8; 16] {
         let mut price_denom_bytes: [u8; 8] = [0; 8];
         let mut asset_denom_bytes: [u8; 8] = [0; 8];
-        let mut bytes = [0 as u8; 16];
+        let mut bytes = [0u8; 16];
 
         self.fill_bytes_from_price_denom(&mut price_denom_bytes);
         self.fill_bytes_from_asset_denom(&mut asset_denom_bytes);
 
-        for i in 0..8 {
-            bytes[i] = price_denom_bytes[i];
-            bytes[i + 8] = asset_denom_bytes[i];
-        }
+        bytes[..8].copy_from_slice(&price_denom_bytes);
+        bytes[8..].copy_from_slice(&asset_denom_bytes);
 
         bytes
     }
 
-    pub fn fill_bytes_from_price_denom(&self, mut bytes: &mut [u8]) {
-        bytes.write(self.price_denom.as_bytes()).unwrap();
+    // copies as much of the denom as fits, truncating the rest
+    pub fn fill_bytes_from_price_denom(&self, bytes: &mut [u8]) {
+        let len = self.price_denom.len().min(bytes.len());
+        bytes[..len].copy_from_slice(&self.price_denom.as_bytes()[..len]);
     }
 
-    pub fn fill_bytes_from_asset_denom(&self, mut bytes: &mut [u8]) {
-        bytes.write(self.asset_denom.as_bytes()).unwrap();
+    // copies as much of the denom as fits, truncating the rest
+    pub fn fill_bytes_from_asset_denom(&self, bytes: &mut [u8]) {
+        let len = self.asset_denom.len().min(bytes.len());
+        bytes[..len].copy_from_slice(&self.asset_denom.as_bytes()[..len]);
     }
 }
 
-// enable Pair to be returned from `range_de()` and friends.
-impl KeyDeserialize for Pair {
-    type Output = Pair;
-
-    fn from_vec(value: Vec<u8>) -> cosmwasm_std::StdResult<Self::Output> {
-        if value.len() != 16 {
-            return Err(StdError::ParseErr {
-                target_type: "pair".to_owned(),
-                msg: "bytes should have a length of 16".to_owned(),
-            });
+// moves the entries of a map stored under the legacy 16 byte pair keys into `map`, which should use
+// a new namespace. Entries can't be recovered from truncated legacy keys alone, so the full pairs
+// have to be passed in; if two of them collided under the legacy encoding the migration fails
+// instead of silently assigning one pair's entry to both. Returns the number of migrated entries.
+// Maps with composite keys containing a pair are migrated by `migrate_legacy_composite_pair_keys`.
+pub fn migrate_legacy_pair_keys<T: Serialize + DeserializeOwned>(
+    storage: &mut dyn Storage,
+    legacy_namespace: &str,
+    map: &Map<Pair, T>,
+    pairs: &[Pair],
+) -> StdResult<u64> {
+    let mut seen: HashMap<[u8; 16], &Pair> = HashMap::new();
+    for pair in pairs {
+        if let Some(other) = seen.insert(pair.legacy_key_bytes(), pair) {
+            return Err(StdError::generic_err(format!(
+                "pairs {}/{} and {}/{} share a legacy storage key",
+                other.price_denom, other.asset_denom, pair.price_denom, pair.asset_denom
+            )));
         }
-        let mut price_denom_last_char_idx = 8;
-        while price_denom_last_char_idx > 0 && value[price_denom_last_char_idx - 1] == 0 {
-            price_denom_last_char_idx -= 1;
+    }
+
+    let mut migrated = 0u64;
+    for pair in pairs {
+        // single element keys are stored as the length-prefixed namespace followed by the key
+        let mut legacy_key = (legacy_namespace.len() as u16).to_be_bytes().to_vec();
+        legacy_key.extend_from_slice(legacy_namespace.as_bytes());
+        legacy_key.extend_from_slice(&pair.legacy_key_bytes());
+        if let Some(raw) = storage.get(&legacy_key) {
+            let value: T = serde_json_wasm::from_slice(&raw)
+                .map_err(|err| StdError::parse_err(std::any::type_name::<T>(), err))?;
+            map.save(storage, pair.clone(), &value)?;
+            storage.remove(&legacy_key);
+            migrated += 1;
         }
-        let price_value = value.get(0..price_denom_last_char_idx).unwrap();
-        let price_denom = std::str::from_utf8(price_value).unwrap().to_string();
-        let mut asset_denom_last_char_idx = 16;
-        while asset_denom_last_char_idx > 8 && value[asset_denom_last_char_idx - 1] == 0 {
-            asset_denom_last_char_idx -= 1;
+    }
+    Ok(migrated)
+}
+
+// `migrate_legacy_pair_keys` for maps keyed by `(K, Pair)`, such as positions or orders keyed by
+// account and pair. Under the legacy encoding the pair was the unprefixed 16 byte suffix of the
+// key, so the entries are found by scanning `legacy_namespace` and matched to the full pairs passed
+// in; the migration fails if an entry's pair isn't among them. Maps where the pair is not the last
+// key element have no migration path and must be rebuilt from their source of truth.
+pub fn migrate_legacy_composite_pair_keys<'a, K, T>(
+    storage: &mut dyn Storage,
+    legacy_namespace: &str,
+    map: &Map<'a, (K, Pair), T>,
+    pairs: &[Pair],
+) -> StdResult<u64>
+where
+    K: PrimaryKey<'a> + Prefixer<'a> + KeyDeserialize<Output = K>,
+    T: Serialize + DeserializeOwned,
+{
+    let mut pairs_by_legacy_key: HashMap<[u8; 16], &Pair> = HashMap::new();
+    for pair in pairs {
+        if let Some(other) = pairs_by_legacy_key.insert(pair.legacy_key_bytes(), pair) {
+            return Err(StdError::generic_err(format!(
+                "pairs {}/{} and {}/{} share a legacy storage key",
+                other.price_denom, other.asset_denom, pair.price_denom, pair.asset_denom
+            )));
         }
-        let asset_value = value.get(8..asset_denom_last_char_idx).unwrap();
-        let asset_denom = std::str::from_utf8(asset_value).unwrap().to_string();
+    }
+
+    let mut namespace_prefix = (legacy_namespace.len() as u16).to_be_bytes().to_vec();
+    namespace_prefix.extend_from_slice(legacy_namespace.as_bytes());
+    let legacy_entries: Vec<(Vec<u8>, Vec<u8>)> = storage
+        .range(
+            Some(&namespace_prefix),
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .take_while(|(key, _)| key.starts_with(&namespace_prefix))
+        .collect();
+
+    let mut migrated = 0u64;
+    for (legacy_key, raw) in legacy_entries {
+        // the first key element is length-prefixed, the legacy pair bytes make up the rest
+        let rest = &legacy_key[namespace_prefix.len()..];
+        let pair = match rest.get(..2) {
+            Some(len) => {
+                let first_len = u16::from_be_bytes([len[0], len[1]]) as usize;
+                rest.get(2 + first_len..)
+                    .filter(|pair_bytes| pair_bytes.len() == 16)
+                    .map(|pair_bytes| (&rest[2..2 + first_len], pair_bytes))
+            }
+            None => None,
+        };
+        let (first, pair_bytes) = pair.ok_or_else(|| {
+            StdError::generic_err(format!(
+                "malformed legacy key in namespace {}",
+                legacy_namespace
+            ))
+        })?;
+        let mut legacy_pair_key = [0u8; 16];
+        legacy_pair_key.copy_from_slice(pair_bytes);
+        let pair = pairs_by_legacy_key.get(&legacy_pair_key).ok_or_else(|| {
+            StdError::generic_err(format!(
+                "no pair given for legacy key {:?} in namespace {}",
+                String::from_utf8_lossy(pair_bytes),
+                legacy_namespace
+            ))
+        })?;
+        let first = K::from_vec(first.to_vec())?;
+        let value: T = serde_json_wasm::from_slice(&raw)
+            .map_err(|err| StdError::parse_err(std::any::type_name::<T>(), err))?;
+        map.save(storage, (first, (*pair).clone()), &value)?;
+        storage.remove(&legacy_key);
+        migrated += 1;
+    }
+    Ok(migrated)
+}
+
+// enable Pair to be returned from `range_de()` and friends. Pair is a two element composite key, so
+// tuples containing a Pair can't use the generic tuple `from_vec`; see `FundingSnapshotKey`.
+impl KeyDeserialize for Pair {
+    type Output = Pair;
 
+    fn from_vec(value: Vec<u8>) -> StdResult<Self::Output> {
+        let (price_denom, asset_denom) = <(String, String)>::from_vec(value)?;
         Ok(Pair {
-            price_denom: price_denom,
-            asset_denom: asset_denom,
+            price_denom,
+            asset_denom,
         })
     }
 }
 
 impl<'a> Prefixer<'a> for Pair {
-    fn prefix(&self) -> Vec<Key> {
-        vec![Key::Val128(self.to_bytes())]
+    fn prefix(&self) -> Vec<Key<'_>> {
+        self.key()
     }
 }
 
-// allow Pair as part of key of cw_storage_plus::Map
+// allow Pair as part of key of cw_storage_plus::Map. Each denom is its own key element so that
+// cw-storage-plus length-prefixes the price denom and denoms of any length stay distinct.
 impl<'a> PrimaryKey<'a> for Pair {
     type Prefix = ();
 
@@ -204,8 +781,151 @@ impl<'a> PrimaryKey<'a> for Pair {
 
     type SuperSuffix = Self;
 
-    fn key(&self) -> Vec<cw_storage_plus::Key> {
-        vec![Key::Val128(self.to_bytes())]
+    fn key(&self) -> Vec<cw_storage_plus::Key<'_>> {
+        vec![
+            Key::Ref(self.price_denom.as_bytes()),
+            Key::Ref(self.asset_denom.as_bytes()),
+        ]
+    }
+}
+
+// human readable identifier of a market, serialized as "{asset_denom}-{price_denom}" (e.g.
+// "ATOM-USDC"), for queries and orders that would otherwise pass both denoms separately. A `-`
+// inside a denom is escaped as `%2D` (and `%` as `%25`), so the separator is always the only bare
+// `-` and every pair has exactly one id.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct MarketId(Pair);
+
+const MARKET_ID_SEPARATOR: char = '-';
+
+fn escape_market_id_denom(denom: &str) -> String {
+    denom
+        .replace('%', "%25")
+        .replace(MARKET_ID_SEPARATOR, "%2D")
+}
+
+// `None` if the denom contains a `%` that doesn't start one of the two escapes, which would not
+// display back as the same id
+fn unescape_market_id_denom(denom: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(denom.len());
+    let mut rest = denom;
+    while let Some(idx) = rest.find('%') {
+        unescaped.push_str(&rest[..idx]);
+        let escape = rest.get(idx..idx + 3)?;
+        unescaped.push(match escape {
+            "%2D" => MARKET_ID_SEPARATOR,
+            "%25" => '%',
+            _ => return None,
+        });
+        rest = &rest[idx + 3..];
+    }
+    unescaped.push_str(rest);
+    Some(unescaped)
+}
+
+impl MarketId {
+    pub fn new(price_denom: &str, asset_denom: &str) -> Result<Self, ContractError> {
+        Pair {
+            price_denom: price_denom.to_owned(),
+            asset_denom: asset_denom.to_owned(),
+        }
+        .try_into()
+    }
+
+    pub fn pair(&self) -> &Pair {
+        &self.0
+    }
+
+    pub fn price_denom(&self) -> &str {
+        &self.0.price_denom
+    }
+
+    pub fn asset_denom(&self) -> &str {
+        &self.0.asset_denom
+    }
+
+    pub fn into_pair(self) -> Pair {
+        self.0
+    }
+}
+
+impl TryFrom<Pair> for MarketId {
+    type Error = ContractError;
+
+    fn try_from(pair: Pair) -> Result<Self, Self::Error> {
+        let market_id = MarketId(pair);
+        if market_id.asset_denom().is_empty() || market_id.price_denom().is_empty() {
+            return Err(ContractError::InvalidMarketId {
+                market_id: market_id.to_string(),
+            });
+        }
+        Ok(market_id)
+    }
+}
+
+impl From<MarketId> for Pair {
+    fn from(market_id: MarketId) -> Self {
+        market_id.0
+    }
+}
+
+impl FromStr for MarketId {
+    type Err = ContractError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ContractError::InvalidMarketId {
+            market_id: s.to_owned(),
+        };
+        match s.split_once(MARKET_ID_SEPARATOR) {
+            Some((asset_denom, price_denom)) if !price_denom.contains(MARKET_ID_SEPARATOR) => {
+                MarketId::new(
+                    &unescape_market_id_denom(price_denom).ok_or_else(invalid)?,
+                    &unescape_market_id_denom(asset_denom).ok_or_else(invalid)?,
+                )
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl TryFrom<String> for MarketId {
+    type Error = ContractError;
+
+    fn try_from(market_id: String) -> Result<Self, Self::Error> {
+        market_id.parse()
+    }
+}
+
+impl From<MarketId> for String {
+    fn from(market_id: MarketId) -> Self {
+        market_id.to_string()
+    }
+}
+
+impl fmt::Display for MarketId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            escape_market_id_denom(&self.0.asset_denom),
+            MARKET_ID_SEPARATOR,
+            escape_market_id_denom(&self.0.price_denom)
+        )
+    }
+}
+
+impl JsonSchema for MarketId {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "MarketId".to_owned()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
     }
 }
 
@@ -226,6 +946,85 @@ pub struct Position {
     // the last paid cumulative funding rate for the position
     // used to calculate remaining payment amount by finding the difference with the current cumulative funding rate
     pub last_paid_funding_payment_rate: SignedDecimal,
+    // positions stored before margin modes existed are cross margined
+    #[serde(default)]
+    pub margin_mode: MarginMode,
+    // collateral (in price denom) allocated to an isolated position; always zero for cross positions
+    #[serde(default = "SignedDecimal::zero")]
+    pub isolated_margin: SignedDecimal,
+}
+
+impl Position {
+    // value of the position at `mark_price`, in price denom
+    pub fn notional(&self, mark_price: Decimal) -> SignedDecimal {
+        self.quantity * SignedDecimal::new(mark_price)
+    }
+
+    // `None` for an empty position
+    pub fn average_entry_price(&self) -> Option<SignedDecimal> {
+        if self.quantity.is_zero() {
+            return None;
+        }
+        Some(exact_ratio(self.total_cost, self.quantity))
+    }
+
+    pub fn unrealized_pnl(&self, mark_price: Decimal) -> SignedDecimal {
+        match self.direction {
+            PositionDirection::Short => self.total_cost - self.notional(mark_price),
+            _ => self.notional(mark_price) - self.total_cost,
+        }
+    }
+
+    // out-of-pocket funds put into the position plus its unrealized pnl
+    pub fn equity(&self, mark_price: Decimal) -> SignedDecimal {
+        self.total_cost - self.total_margin_debt + self.unrealized_pnl(mark_price)
+    }
+
+    // equity over notional; `None` for an empty position
+    pub fn margin_ratio(&self, mark_price: Decimal) -> Option<SignedDecimal> {
+        let notional = self.notional(mark_price);
+        if notional.is_zero() {
+            return None;
+        }
+        Some(exact_ratio(self.equity(mark_price), notional))
+    }
+
+    pub fn is_liquidatable(&self, mark_price: Decimal, maintenance: Decimal) -> bool {
+        match self.margin_ratio(mark_price) {
+            Some(ratio) => ratio < SignedDecimal::new(maintenance),
+            None => false,
+        }
+    }
+}
+
+// `numerator / denominator` from the full-precision ratio of the atomics. `SignedDecimal`'s `/`
+// multiplies by a truncated reciprocal (e.g. 60 / 120 = 0.49999999999999998), which would put a
+// position sitting exactly at its maintenance ratio below it
+fn exact_ratio(numerator: SignedDecimal, denominator: SignedDecimal) -> SignedDecimal {
+    let ratio = Decimal::from_ratio(numerator.decimal.atomics(), denominator.decimal.atomics());
+    if numerator.negative != denominator.negative && !ratio.is_zero() {
+        SignedDecimal::new_negative(ratio)
+    } else {
+        SignedDecimal::new(ratio)
+    }
+}
+
+// `Cross` positions share the account's whole collateral; `Isolated` positions are backed only by the
+// margin allocated to them, capping the loss to that margin
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, JsonSchema, Eq, Hash)]
+pub enum MarginMode {
+    #[default]
+    Cross,
+    Isolated,
+}
+
+impl fmt::Display for MarginMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarginMode::Cross => write!(f, "Cross"),
+            MarginMode::Isolated => write!(f, "Isolated"),
+        }
+    }
 }
 
 pub fn opposite_direction(direction: PositionDirection) -> PositionDirection {
@@ -242,3 +1041,1011 @@ pub struct MarginRatios {
     pub partial: Decimal,
     pub maintenance: Decimal,
 }
+
+impl MarginRatios {
+    pub fn is_liquidatable(&self, margin_ratio: Decimal) -> bool {
+        margin_ratio < self.maintenance
+    }
+
+    // below the partial ratio but still above maintenance: only part of the portfolio gets liquidated
+    pub fn is_in_partial_liquidation_band(&self, margin_ratio: Decimal) -> bool {
+        margin_ratio < self.partial && margin_ratio >= self.maintenance
+    }
+
+    // negative once the account is liquidatable
+    pub fn distance_to_liquidation(&self, margin_ratio: Decimal) -> SignedDecimal {
+        SignedDecimal::new(margin_ratio) - SignedDecimal::new(self.maintenance)
+    }
+}
+
+// liquidity-mining emission for a pair. Every epoch `emission_per_epoch` of `reward_denom` is split
+// between makers and takers according to the shares, then pro rata to each account's volume.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardsConfig {
+    pub reward_denom: String,
+    pub emission_per_epoch: Decimal,
+    pub maker_share: Decimal,
+    pub taker_share: Decimal,
+}
+
+// trading volume accumulated within an epoch, tracked both per pair and per account in the pair
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct RewardsAccumulator {
+    pub epoch: i64,
+    pub maker_volume: Decimal,
+    pub taker_volume: Decimal,
+}
+
+impl RewardsAccumulator {
+    pub fn new(epoch: i64) -> Self {
+        RewardsAccumulator {
+            epoch,
+            maker_volume: Decimal::zero(),
+            taker_volume: Decimal::zero(),
+        }
+    }
+
+    pub fn record(&mut self, volume: Decimal, is_maker: bool) {
+        if is_maker {
+            self.maker_volume += volume;
+        } else {
+            self.taker_volume += volume;
+        }
+    }
+
+    // rewards earned for `self` (an account's volume) given the pair total for the same epoch. The
+    // volume share is applied as a ratio with a 256 bit intermediate, so only a reward that doesn't
+    // fit a `Decimal` overflows
+    pub fn accrued_rewards(
+        &self,
+        pair_total: &RewardsAccumulator,
+        config: &RewardsConfig,
+    ) -> Result<Decimal, ContractError> {
+        let maker_rewards = volume_share_of(
+            config.emission_per_epoch.checked_mul(config.maker_share)?,
+            self.maker_volume,
+            pair_total.maker_volume,
+        )?;
+        let taker_rewards = volume_share_of(
+            config.emission_per_epoch.checked_mul(config.taker_share)?,
+            self.taker_volume,
+            pair_total.taker_volume,
+        )?;
+        Ok(maker_rewards.checked_add(taker_rewards)?)
+    }
+}
+
+// `amount * volume / total_volume`, zero if there was no volume at all
+fn volume_share_of(
+    amount: Decimal,
+    volume: Decimal,
+    total_volume: Decimal,
+) -> Result<Decimal, ContractError> {
+    if total_volume.is_zero() {
+        return Ok(Decimal::zero());
+    }
+    let atomics = amount
+        .atomics()
+        .checked_multiply_ratio(volume.atomics(), total_volume.atomics())?;
+    Ok(Decimal::new(atomics))
+}
+
+// dead man's switch registered by an account. Sending `SetCancelOnDisconnect` again refreshes it;
+// if the account stays silent for longer than `ttl_epochs`, all of its resting orders are swept
+// during `NewBlock` processing.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Copy)]
+pub struct CancelOnDisconnect {
+    pub ttl_epochs: u64,
+    pub last_refresh_epoch: i64,
+}
+
+impl CancelOnDisconnect {
+    // a ttl of 0 means the switch is disabled, so it never expires
+    pub fn is_expired(&self, current_epoch: i64) -> bool {
+        if self.ttl_epochs == 0 {
+            return false;
+        }
+        match u64::try_from(current_epoch.saturating_sub(self.last_refresh_epoch)) {
+            Ok(elapsed) => elapsed > self.ttl_epochs,
+            // refreshed in a later epoch than `current_epoch`
+            Err(_) => false,
+        }
+    }
+}
+
+// wall-clock counterpart of `CancelOnDisconnect`: every `SetCancelAfter` pushes the deadline to
+// `seconds` after the current block time, and once a block's time passes the deadline the
+// account's resting orders are cancelled during `NewBlock` processing
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Copy)]
+pub struct CancelAfter {
+    pub seconds: u64,
+    // unix seconds
+    pub deadline: u64,
+}
+
+impl CancelAfter {
+    pub fn new(seconds: u64, block_time: u64) -> Self {
+        CancelAfter {
+            seconds,
+            deadline: block_time.saturating_add(seconds),
+        }
+    }
+
+    // a `seconds` of 0 means the switch is disarmed, so it never expires
+    pub fn is_expired(&self, block_time: u64) -> bool {
+        self.seconds != 0 && block_time > self.deadline
+    }
+}
+
+// an account's portfolio value sampled at the end of an epoch
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EquitySnapshot {
+    pub epoch: i64,
+    pub equity: SignedDecimal,
+    pub total_position_value: SignedDecimal,
+    pub unrealized_pnl: SignedDecimal,
+}
+
+// what a pair falls back to once its oracle price is considered stale
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, JsonSchema, Eq, Hash)]
+pub enum StalePricePolicy {
+    #[default]
+    HaltTrading,
+    UseLastTwap,
+    UseSpotMarket,
+}
+
+impl StalePricePolicy {
+    // `fresh_oracle_price` should be `None` when the latest oracle observation is stale
+    pub fn resolve_price(
+        &self,
+        fresh_oracle_price: Option<Decimal>,
+        last_twap: Option<Decimal>,
+        spot_price: Option<Decimal>,
+    ) -> Result<Decimal, ContractError> {
+        if let Some(price) = fresh_oracle_price {
+            return Ok(price);
+        }
+        match self {
+            StalePricePolicy::HaltTrading => Err(ContractError::StalePrice {}),
+            StalePricePolicy::UseLastTwap => last_twap.ok_or(ContractError::TwapNotExist {}),
+            StalePricePolicy::UseSpotMarket => spot_price.ok_or(ContractError::StalePrice {}),
+        }
+    }
+}
+
+// which side of the denom mappings a denom passed to `ResolveDenom` is on. `Internal` denoms are the
+// ones the contract keeps balances in; `External` are full (bank/cw20) denoms.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, JsonSchema, Eq, Hash)]
+pub enum DenomDirection {
+    ExternalToInternal,
+    InternalToExternal,
+}
+
+// risk tier an account is assigned to by the risk manager, capping its total notional exposure and
+// leverage. Accounts without an explicit assignment fall into the contract's default tier.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AccountTier {
+    pub tier: u32,
+    pub max_notional: Decimal,
+    pub max_leverage: Decimal,
+}
+
+impl AccountTier {
+    pub fn check_exposure(
+        &self,
+        notional: Decimal,
+        leverage: Decimal,
+    ) -> Result<(), ContractError> {
+        if notional > self.max_notional {
+            return Err(ContractError::ExposureCapExceeded {
+                tier: self.tier,
+                max_notional: self.max_notional,
+            });
+        }
+        if leverage > self.max_leverage {
+            return Err(ContractError::LeverageCapExceeded {
+                tier: self.tier,
+                max_leverage: self.max_leverage,
+            });
+        }
+        Ok(())
+    }
+}
+
+// position size limits of the accounts assigned to the tier, checked at order placement against
+// the notional the account would hold if the order were fully filled
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionLimitTier {
+    pub tier: u32,
+    pub max_notional_per_market: Decimal,
+    pub max_total_notional: Decimal,
+}
+
+impl PositionLimitTier {
+    pub fn check_position_size(
+        &self,
+        market_notional: Decimal,
+        total_notional: Decimal,
+    ) -> Result<(), ContractError> {
+        if market_notional > self.max_notional_per_market {
+            return Err(ContractError::PositionLimitExceeded {
+                tier: self.tier,
+                limit: self.max_notional_per_market,
+            });
+        }
+        if total_notional > self.max_total_notional {
+            return Err(ContractError::PositionLimitExceeded {
+                tier: self.tier,
+                limit: self.max_total_notional,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LiquidationPenaltyPoint {
+    pub shortfall: Decimal,
+    pub penalty: Decimal,
+}
+
+// liquidation penalty as a piecewise linear function of the margin shortfall (how far the account is
+// below its maintenance requirement, as a fraction of it). Points must be sorted by strictly
+// increasing shortfall; outside of the covered range the penalty of the nearest point applies.
+// When set, this replaces the flat `liquidation_order_fee`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LiquidationPenaltyCurve {
+    pub points: Vec<LiquidationPenaltyPoint>,
+}
+
+impl LiquidationPenaltyCurve {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.points.is_empty() {
+            return Err(ContractError::InvalidLiquidationPenaltyCurve {});
+        }
+        for window in self.points.windows(2) {
+            if window[0].shortfall >= window[1].shortfall {
+                return Err(ContractError::InvalidLiquidationPenaltyCurve {});
+            }
+        }
+        Ok(())
+    }
+
+    pub fn penalty(&self, shortfall: Decimal) -> Decimal {
+        let first = match self.points.first() {
+            Some(point) => point,
+            None => return Decimal::zero(),
+        };
+        if shortfall <= first.shortfall {
+            return first.penalty;
+        }
+        for window in self.points.windows(2) {
+            let (lower, upper) = (&window[0], &window[1]);
+            if shortfall <= upper.shortfall {
+                // scale the penalty step by the progress through the segment in a single ratio,
+                // so that no intermediate value is truncated
+                let step = |from: Decimal, to: Decimal| {
+                    Decimal::new((to - from).atomics().multiply_ratio(
+                        (shortfall - lower.shortfall).atomics(),
+                        (upper.shortfall - lower.shortfall).atomics(),
+                    ))
+                };
+                return if upper.penalty >= lower.penalty {
+                    lower.penalty + step(lower.penalty, upper.penalty)
+                } else {
+                    lower.penalty - step(upper.penalty, lower.penalty)
+                };
+            }
+        }
+        self.points.last().unwrap().penalty
+    }
+}
+
+// currency in which funding payments of a market are charged and paid. Defaults to the market's
+// price denom; for markets quoted in a non-base price denom it can be set to e.g. the base denom.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FundingSettlementConfig {
+    pub funding_settlement_denom: String,
+}
+
+impl FundingSettlementConfig {
+    pub fn new(pair: &Pair) -> Self {
+        FundingSettlementConfig {
+            funding_settlement_denom: pair.price_denom.to_owned(),
+        }
+    }
+
+    pub fn settles_in_price_denom(&self, pair: &Pair) -> bool {
+        self.funding_settlement_denom == pair.price_denom
+    }
+
+    // `price_denom_rate` is the value of one unit of price denom in the settlement denom
+    pub fn to_settlement_amount(
+        &self,
+        amount_in_price_denom: SignedDecimal,
+        price_denom_rate: Decimal,
+    ) -> SignedDecimal {
+        amount_in_price_denom * SignedDecimal::new(price_denom_rate)
+    }
+
+    pub fn to_price_denom_amount(
+        &self,
+        amount_in_settlement_denom: SignedDecimal,
+        price_denom_rate: Decimal,
+    ) -> SignedDecimal {
+        amount_in_settlement_denom / SignedDecimal::new(price_denom_rate)
+    }
+}
+
+// bounds on the funding rate of a single epoch so that a temporary oracle dislocation can't
+// produce outsized funding payments
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FundingRateClamp {
+    pub funding_rate_cap: SignedDecimal,
+    pub funding_rate_floor: SignedDecimal,
+}
+
+impl FundingRateClamp {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.funding_rate_floor > self.funding_rate_cap {
+            return Err(ContractError::InvalidFundingRateClamp {});
+        }
+        Ok(())
+    }
+
+    pub fn clamp(&self, rate: SignedDecimal) -> SignedDecimal {
+        if rate > self.funding_rate_cap {
+            self.funding_rate_cap
+        } else if rate < self.funding_rate_floor {
+            self.funding_rate_floor
+        } else {
+            rate
+        }
+    }
+}
+
+// an executed fill of one of the account's orders. `id` is unique per account and increasing, and is
+// what trade history pagination is keyed by.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TradeRecord {
+    pub id: u64,
+    pub fill: Fill,
+}
+
+// fees of a single market that differ from the global ones
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MarketFeeOverride {
+    pub pair: Pair,
+    pub limit_order_fee: SignedDecimal,
+    pub market_order_fee: SignedDecimal,
+}
+
+// `ListMarkets` used to return this reduced view of a market; it is now the full `Market`
+#[deprecated(note = "use `Market`")]
+pub type MarketInfo = Market;
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, JsonSchema, Eq, Hash)]
+pub enum MarketStatus {
+    #[default]
+    Active,
+    // only orders that reduce existing positions are accepted
+    ReduceOnly,
+    Halted,
+}
+
+impl fmt::Display for MarketStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarketStatus::Active => write!(f, "Active"),
+            MarketStatus::ReduceOnly => write!(f, "ReduceOnly"),
+            MarketStatus::Halted => write!(f, "Halted"),
+        }
+    }
+}
+
+// configuration and trading rules of a single market. Prices must be multiples of `tick_size`,
+// quantities multiples of `lot_size`, and every order's notional at least `min_notional`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Market {
+    pub pair: Pair,
+    pub tick_size: Decimal,
+    pub lot_size: Decimal,
+    pub min_notional: Decimal,
+    pub max_leverage: SignedDecimal,
+    pub margin_ratios: MarginRatios,
+    // whether the pair is one of the funding payment pairs
+    pub funding_enabled: bool,
+    #[serde(default)]
+    pub status: MarketStatus,
+    // cap on the open interest of each side, in asset units. `None` means uncapped
+    #[serde(default)]
+    pub max_open_interest: Option<Decimal>,
+    // `None` means funding is settled in the price denom
+    #[serde(default)]
+    pub funding_settlement_denom: Option<String>,
+}
+
+impl Market {
+    pub fn funding_settlement_config(&self) -> FundingSettlementConfig {
+        match &self.funding_settlement_denom {
+            Some(denom) => FundingSettlementConfig {
+                funding_settlement_denom: denom.to_owned(),
+            },
+            None => FundingSettlementConfig::new(&self.pair),
+        }
+    }
+
+    pub fn market_id(&self) -> Result<MarketId, ContractError> {
+        self.pair.clone().try_into()
+    }
+
+    pub fn validate_order(
+        &self,
+        price: Decimal,
+        quantity: Decimal,
+        reduce_only: bool,
+    ) -> Result<(), ContractError> {
+        match self.status {
+            MarketStatus::Active => {}
+            MarketStatus::ReduceOnly if reduce_only => {}
+            status => return Err(ContractError::MarketNotActive { status }),
+        }
+        if !is_multiple_of(price, self.tick_size) {
+            return Err(ContractError::InvalidTickSize {
+                tick_size: self.tick_size,
+            });
+        }
+        if !is_multiple_of(quantity, self.lot_size) {
+            return Err(ContractError::InvalidLotSize {
+                lot_size: self.lot_size,
+            });
+        }
+        if price.checked_mul(quantity)? < self.min_notional {
+            return Err(ContractError::BelowMinNotional {
+                min_notional: self.min_notional,
+            });
+        }
+        Ok(())
+    }
+}
+
+// a step of zero means the value is unconstrained
+fn is_multiple_of(value: Decimal, step: Decimal) -> bool {
+    step.is_zero() || (value.atomics() % step.atomics()).is_zero()
+}
+
+// parts of the contract state that can be exported with `ExportState`
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, JsonSchema, Eq, Hash)]
+pub enum StateSection {
+    Positions,
+    Balances,
+    Orders,
+    FundingRates,
+}
+
+// activities that can be halted by the circuit breaker. `All` covers every other scope.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, JsonSchema, Eq, Hash)]
+pub enum PauseScope {
+    Trading,
+    Withdrawals,
+    Liquidations,
+    All,
+}
+
+impl PauseScope {
+    pub fn covers(&self, scope: PauseScope) -> bool {
+        *self == PauseScope::All || *self == scope
+    }
+}
+
+impl fmt::Display for PauseScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PauseScope::Trading => write!(f, "Trading"),
+            PauseScope::Withdrawals => write!(f, "Withdrawals"),
+            PauseScope::Liquidations => write!(f, "Liquidations"),
+            PauseScope::All => write!(f, "All"),
+        }
+    }
+}
+
+// whether an account provided (maker) or took (taker) liquidity in a fill, which determines its fee
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, JsonSchema, Eq, Hash)]
+pub enum LiquiditySide {
+    Maker,
+    Taker,
+}
+
+// volume-based fee discount: accounts whose 30 day rolling volume is at least `min_30d_volume` pay
+// this tier's fees
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeTier {
+    pub min_30d_volume: Decimal,
+    pub maker_fee: SignedDecimal,
+    pub taker_fee: SignedDecimal,
+}
+
+// the tier with the highest volume threshold the account qualifies for
+pub fn select_fee_tier(tiers: &[FeeTier], volume: Decimal) -> Option<&FeeTier> {
+    tiers
+        .iter()
+        .filter(|tier| tier.min_30d_volume <= volume)
+        .max_by(|a, b| a.min_30d_volume.cmp(&b.min_30d_volume))
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VolumeBucket {
+    pub day: u64,
+    pub volume: Decimal,
+}
+
+// an account's trading volume bucketed per day (unix seconds / 86400), keeping only the buckets
+// within the rolling window
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct RollingVolume {
+    pub buckets: Vec<VolumeBucket>,
+}
+
+impl RollingVolume {
+    pub const WINDOW_DAYS: u64 = 30;
+
+    pub fn day_of(timestamp_seconds: u64) -> u64 {
+        timestamp_seconds / 86400
+    }
+
+    pub fn record(&mut self, day: u64, volume: Decimal) {
+        match self.buckets.iter_mut().find(|bucket| bucket.day == day) {
+            Some(bucket) => bucket.volume += volume,
+            None => self.buckets.push(VolumeBucket { day, volume }),
+        }
+        self.buckets
+            .retain(|bucket| bucket.day + RollingVolume::WINDOW_DAYS > day);
+    }
+
+    pub fn total(&self, current_day: u64) -> Decimal {
+        self.buckets
+            .iter()
+            .filter(|bucket| bucket.day + RollingVolume::WINDOW_DAYS > current_day)
+            .fold(Decimal::zero(), |acc, bucket| acc + bucket.volume)
+    }
+}
+
+// fee discount granted to accounts staking or holding at least `min_staked` of the native token.
+// `discount` is the fraction of the fee waived.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakingDiscountTier {
+    pub min_staked: Decimal,
+    pub discount: Decimal,
+}
+
+pub fn select_staking_discount(tiers: &[StakingDiscountTier], staked: Decimal) -> Decimal {
+    tiers
+        .iter()
+        .filter(|tier| tier.min_staked <= staked)
+        .map(|tier| tier.discount)
+        .max()
+        .unwrap_or_else(Decimal::zero)
+}
+
+// rebates (negative fees) are left untouched
+pub fn apply_fee_discount(fee: SignedDecimal, discount: Decimal) -> SignedDecimal {
+    if fee.negative {
+        return fee;
+    }
+    if discount >= Decimal::one() {
+        return SignedDecimal::zero();
+    }
+    fee * SignedDecimal::new(Decimal::one() - discount)
+}
+
+// a profitable position eligible for auto-deleveraging. Positions are reduced in order of decreasing
+// `ranking_score`, ties broken by account so that the queue order is deterministic.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdlQueueEntry {
+    pub account: String,
+    pub pair: Pair,
+    pub direction: PositionDirection,
+    pub ranking_score: SignedDecimal,
+}
+
+impl AdlQueueEntry {
+    // the usual profit-and-leverage ranking: the more profitable and the more levered a position is,
+    // the earlier it gets deleveraged
+    pub fn ranking_score(
+        unrealized_pnl_ratio: SignedDecimal,
+        leverage: SignedDecimal,
+    ) -> SignedDecimal {
+        unrealized_pnl_ratio * leverage
+    }
+}
+
+pub fn sort_adl_queue(entries: &mut [AdlQueueEntry]) {
+    entries.sort_by(|a, b| {
+        b.ranking_score
+            .cmp(&a.ranking_score)
+            .then_with(|| a.account.cmp(&b.account))
+    });
+}
+
+// take-profit / stop-loss prices attached to a position rather than to individual orders, so they
+// survive order cancellations. The position is closed at market once the mark price crosses either.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionTrigger {
+    pub pair: Pair,
+    pub direction: PositionDirection,
+    pub take_profit: Option<Decimal>,
+    pub stop_loss: Option<Decimal>,
+}
+
+impl PositionTrigger {
+    pub fn is_triggered(&self, mark_price: Decimal) -> bool {
+        match self.direction {
+            PositionDirection::Long => {
+                matches!(self.take_profit, Some(tp) if mark_price >= tp)
+                    || matches!(self.stop_loss, Some(sl) if mark_price <= sl)
+            }
+            PositionDirection::Short => {
+                matches!(self.take_profit, Some(tp) if mark_price <= tp)
+                    || matches!(self.stop_loss, Some(sl) if mark_price >= sl)
+            }
+            PositionDirection::Unknown => false,
+        }
+    }
+}
+
+// set of actions an operator may perform on behalf of the account that granted it. Serialized as
+// the raw bits so that permissions can be combined, e.g. `Permission::TRADE | Permission::WITHDRAW`.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, Eq, Hash, JsonSchema)]
+#[serde(transparent)]
+pub struct Permission(pub u8);
+
+impl Permission {
+    pub const TRADE: Permission = Permission(1);
+    pub const WITHDRAW: Permission = Permission(1 << 1);
+    pub const MANAGE: Permission = Permission(1 << 2);
+
+    pub const fn empty() -> Self {
+        Permission(0)
+    }
+
+    pub const fn all() -> Self {
+        Permission(Self::TRADE.0 | Self::WITHDRAW.0 | Self::MANAGE.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, other: Permission) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    // drops bits that don't correspond to any known permission
+    pub fn truncate(&self) -> Self {
+        Permission(self.0 & Self::all().0)
+    }
+}
+
+impl BitOr for Permission {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Permission(self.0 | rhs.0)
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<&str> = [
+            (Self::TRADE, "Trade"),
+            (Self::WITHDRAW, "Withdraw"),
+            (Self::MANAGE, "Manage"),
+        ]
+        .iter()
+        .filter(|(permission, _)| self.contains(*permission))
+        .map(|(_, name)| *name)
+        .collect();
+        write!(f, "{}", names.join("|"))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OperatorGrant {
+    pub operator: String,
+    pub permissions: Permission,
+}
+
+// a withdrawal waiting out the configured delay. The coins stay locked in the account until the
+// withdrawal is claimed or cancelled.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingWithdrawal {
+    pub id: u64,
+    pub account: String,
+    pub recipient: String,
+    pub coins: Vec<Coin>,
+    // unix seconds
+    pub requested_at: u64,
+    pub claimable_at: u64,
+}
+
+impl PendingWithdrawal {
+    pub fn is_claimable(&self, block_time: u64) -> bool {
+        block_time >= self.claimable_at
+    }
+}
+
+// cap on the total amount of `denom` withdrawn from the contract within a single epoch, across all
+// accounts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawalLimit {
+    pub denom: String,
+    pub max_per_epoch: Uint128,
+}
+
+impl WithdrawalLimit {
+    pub fn check(
+        &self,
+        withdrawn_this_epoch: Uint128,
+        amount: Uint128,
+    ) -> Result<(), ContractError> {
+        let remaining = self.max_per_epoch.saturating_sub(withdrawn_this_epoch);
+        if amount > remaining {
+            return Err(ContractError::WithdrawalLimitExceeded {
+                denom: self.denom.to_owned(),
+                remaining,
+            });
+        }
+        Ok(())
+    }
+}
+
+// a single match of an order against the book, as reported in `FinalizeBlock` results, stored in
+// trade history and emitted in `order_filled` events
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Fill {
+    pub order_id: u64,
+    pub account: String,
+    pub pair: Pair,
+    pub direction: PositionDirection,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub fee: DecimalCoin,
+    // whether the order was resting on the book when this fill happened
+    pub maker: bool,
+    pub epoch: i64,
+    // unix seconds; zero if the source didn't report it
+    #[serde(default)]
+    pub timestamp: u64,
+}
+
+impl Fill {
+    pub fn notional(&self) -> Decimal {
+        self.price * self.quantity
+    }
+
+    pub fn liquidity_side(&self) -> LiquiditySide {
+        if self.maker {
+            LiquiditySide::Maker
+        } else {
+            LiquiditySide::Taker
+        }
+    }
+}
+
+// aggregated resting orders at a single price
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderBookLevel {
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub order_count: u32,
+}
+
+// bids are sorted from the highest price down, asks from the lowest price up
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderBookSnapshot {
+    pub pair: Pair,
+    pub bids: Vec<OrderBookLevel>,
+    pub asks: Vec<OrderBookLevel>,
+    pub epoch: i64,
+}
+
+impl OrderBookSnapshot {
+    pub fn best_bid(&self) -> Option<&OrderBookLevel> {
+        self.bids.first()
+    }
+
+    pub fn best_ask(&self) -> Option<&OrderBookLevel> {
+        self.asks.first()
+    }
+
+    pub fn mid_price(&self) -> Option<Decimal> {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) => {
+                Some((bid.price + ask.price) / Decimal::from_ratio(2u128, 1u128))
+            }
+            _ => None,
+        }
+    }
+}
+
+// pagination parameters shared by all list queries. `start_after` is the opaque `next_key` of the
+// previous page; the first page is requested with the default value. Cursors are always the JSON
+// encoding of the storage key, see `encode_next_key`; raw byte keys are encoded as `Binary`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct PageRequest {
+    pub start_after: Option<Binary>,
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub reverse: bool,
+}
+
+impl PageRequest {
+    pub fn limit(&self, default_limit: u32, max_limit: u32) -> usize {
+        self.limit.unwrap_or(default_limit).min(max_limit) as usize
+    }
+
+    // decodes `start_after` into the storage key the previous page ended at
+    pub fn start_after_key<K: DeserializeOwned>(&self) -> StdResult<Option<K>> {
+        self.start_after
+            .as_ref()
+            .map(|key| {
+                serde_json_wasm::from_slice(key.as_slice())
+                    .map_err(|err| StdError::parse_err(std::any::type_name::<K>(), err))
+            })
+            .transpose()
+    }
+}
+
+// `next_key` is `None` on the last page
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PageResponse<T> {
+    pub items: Vec<T>,
+    pub next_key: Option<Binary>,
+}
+
+impl<T> PageResponse<T> {
+    pub fn new<K: Serialize>(items: Vec<T>, next_key: Option<&K>) -> StdResult<Self> {
+        Ok(PageResponse {
+            items,
+            next_key: encode_next_key(next_key)?,
+        })
+    }
+}
+
+// cursor encoding used by `PageResponse::new`, for list responses that carry more than the items
+pub fn encode_next_key<K: Serialize>(next_key: Option<&K>) -> StdResult<Option<Binary>> {
+    next_key
+        .map(|key| {
+            serde_json_wasm::to_vec(key)
+                .map(Binary::from)
+                .map_err(|err| StdError::serialize_err(std::any::type_name::<K>(), err))
+        })
+        .transpose()
+}
+
+// a denom that has been checked to be well formed: a native denom (`usei`), a token factory denom
+// (`factory/{creator}/{subdenom}`), an IBC denom (`ibc/{sha256 hash}`) or a cw20 token, which is
+// referred to by its contract address as in `AddToCW20DenomMapping`. Only the format is checked,
+// not whether the denom exists on chain.
+#[derive(
+    Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, JsonSchema,
+)]
+#[serde(try_from = "String", into = "String")]
+pub struct Denom(String);
+
+const FACTORY_DENOM_PREFIX: &str = "factory/";
+const IBC_DENOM_PREFIX: &str = "ibc/";
+
+impl Denom {
+    pub fn new(denom: impl Into<String>) -> Result<Self, ContractError> {
+        let denom = denom.into();
+        let valid = if let Some(rest) = denom.strip_prefix(FACTORY_DENOM_PREFIX) {
+            match rest.split_once('/') {
+                Some((creator, subdenom)) => {
+                    is_valid_address_format(creator) && is_valid_native_denom(subdenom, 1)
+                }
+                None => false,
+            }
+        } else if let Some(hash) = denom.strip_prefix(IBC_DENOM_PREFIX) {
+            hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())
+        } else {
+            is_valid_native_denom(&denom, 3)
+        };
+        if !valid {
+            return Err(ContractError::MalformedDenom { denom });
+        }
+        Ok(Denom(denom))
+    }
+
+    // also checks the address' bech32 encoding and prefix, which `new` can't do without an `Api`
+    pub fn cw20(api: &dyn Api, address: &str) -> Result<Self, ContractError> {
+        let address = api.addr_validate(address)?;
+        Ok(Denom(address.into_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    // native denoms never have the shape of an account or contract address
+    pub fn is_cw20(&self) -> bool {
+        is_valid_address_format(&self.0)
+    }
+
+    pub fn is_factory(&self) -> bool {
+        self.0.starts_with(FACTORY_DENOM_PREFIX)
+    }
+
+    pub fn is_ibc(&self) -> bool {
+        self.0.starts_with(IBC_DENOM_PREFIX)
+    }
+
+    // the denom without its namespace: the subdenom of a factory denom, the hash of an IBC denom and
+    // the denom itself for native denoms and cw20 tokens
+    pub fn base_denom(&self) -> &str {
+        if let Some(rest) = self.0.strip_prefix(FACTORY_DENOM_PREFIX) {
+            rest.split_once('/').map_or(rest, |(_, subdenom)| subdenom)
+        } else if let Some(hash) = self.0.strip_prefix(IBC_DENOM_PREFIX) {
+            hash
+        } else {
+            &self.0
+        }
+    }
+}
+
+// follows the sdk's native denom rules: a letter followed by letters, digits or `/:._-`, up to 128
+// characters in total
+fn is_valid_native_denom(denom: &str, min_len: usize) -> bool {
+    let mut chars = denom.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() => {}
+        _ => return false,
+    }
+    denom.len() >= min_len
+        && denom.len() <= 128
+        && chars.all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c))
+}
+
+// lowercase bech32 shape: a human readable prefix, the `1` separator and a data part that holds at
+// least a 20 byte address (32 characters) and the 6 character checksum
+fn is_valid_address_format(address: &str) -> bool {
+    match address.rsplit_once('1') {
+        Some((hrp, data)) => {
+            !hrp.is_empty()
+                && data.len() >= 38
+                && address
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+impl TryFrom<String> for Denom {
+    type Error = ContractError;
+
+    fn try_from(denom: String) -> Result<Self, Self::Error> {
+        Denom::new(denom)
+    }
+}
+
+impl From<Denom> for String {
+    fn from(denom: Denom) -> Self {
+        denom.0
+    }
+}
+
+impl FromStr for Denom {
+    type Err = ContractError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Denom::new(s)
+    }
+}
+
+impl fmt::Display for Denom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
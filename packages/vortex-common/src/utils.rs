@@ -1,3 +1,4 @@
+use crate::error::ContractError;
 use cosmwasm_std::{Decimal, DecimalRangeExceeded, Fraction, Uint128};
 use forward_ref::{forward_ref_binop, forward_ref_op_assign};
 use schemars::JsonSchema;
@@ -28,7 +29,7 @@ impl SignedDecimal {
 
     pub const fn new(decimal: Decimal) -> Self {
         SignedDecimal {
-            decimal: decimal,
+            decimal,
             negative: false,
         }
     }
@@ -42,16 +43,13 @@ impl SignedDecimal {
 
     pub const fn new_negative(decimal: Decimal) -> Self {
         SignedDecimal {
-            decimal: decimal,
+            decimal,
             negative: true,
         }
     }
 
     pub const fn new_signed(decimal: Decimal, negative: bool) -> Self {
-        SignedDecimal {
-            decimal: decimal,
-            negative: negative,
-        }
+        SignedDecimal { decimal, negative }
     }
 
     pub fn from_atomics(
@@ -60,10 +58,7 @@ impl SignedDecimal {
         negative: bool,
     ) -> Result<Self, DecimalRangeExceeded> {
         match Decimal::from_atomics(atomics, decimal_places) {
-            Ok(decimal) => Result::Ok(SignedDecimal {
-                decimal: decimal,
-                negative: negative,
-            }),
+            Ok(decimal) => Result::Ok(SignedDecimal { decimal, negative }),
             Err(err) => Result::Err(err),
         }
     }
@@ -72,10 +67,10 @@ impl SignedDecimal {
         if self.decimal == Decimal::zero() {
             return *self;
         }
-        return SignedDecimal {
+        SignedDecimal {
             decimal: self.decimal,
             negative: !self.negative,
-        };
+        }
     }
 
     pub fn is_zero(&self) -> bool {
@@ -156,7 +151,7 @@ impl Add for SignedDecimal {
                 }
             }
         } else {
-            assert_eq!(!self.negative && !other.negative, true);
+            assert!(!self.negative && !other.negative);
             SignedDecimal {
                 decimal: self.decimal + other.decimal,
                 negative: false,
@@ -271,6 +266,148 @@ impl fmt::Display for SignedDecimal {
     }
 }
 
+// split `total` across `weights` proportionally. The truncating decimal math leaves some rounding
+// dust behind, which is assigned to the largest weight by magnitude (the first one on ties) so that the returned
+// shares always add up to exactly `total`. Weights that sum to zero or that mix signs have no
+// meaningful proportions and are rejected, since `total` would otherwise be silently lost.
+pub fn allocate_pro_rata(
+    total: SignedDecimal,
+    weights: &[SignedDecimal],
+) -> Result<Vec<SignedDecimal>, ContractError> {
+    let mut nonzero_weights = weights.iter().filter(|weight| !weight.is_zero());
+    let negative = nonzero_weights.next().map(|weight| weight.negative);
+    if negative.is_none() || nonzero_weights.any(|weight| Some(weight.negative) != negative) {
+        return Err(ContractError::InvalidAllocationWeights {});
+    }
+    let total_weight = weights
+        .iter()
+        .fold(SignedDecimal::zero(), |acc, weight| acc + *weight);
+
+    let mut shares: Vec<SignedDecimal> = weights
+        .iter()
+        .map(|weight| (*weight / total_weight) * total)
+        .collect();
+    let allocated = shares
+        .iter()
+        .fold(SignedDecimal::zero(), |acc, share| acc + *share);
+
+    let mut largest_idx = 0;
+    for (idx, weight) in weights.iter().enumerate() {
+        if weight.decimal > weights[largest_idx].decimal {
+            largest_idx = idx;
+        }
+    }
+    shares[largest_idx] += total - allocated;
+    Ok(shares)
+}
+
+// Dimension-tagged wrappers around SignedDecimal. Only the operations that make dimensional sense
+// are implemented (e.g. Price * Quantity = Notional), so adding a price to a quantity fails to compile.
+#[derive(
+    Copy, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, JsonSchema, Debug,
+)]
+#[serde(transparent)]
+pub struct Price(pub SignedDecimal);
+
+#[derive(
+    Copy, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, JsonSchema, Debug,
+)]
+#[serde(transparent)]
+pub struct Quantity(pub SignedDecimal);
+
+#[derive(
+    Copy, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, JsonSchema, Debug,
+)]
+#[serde(transparent)]
+pub struct Notional(pub SignedDecimal);
+
+macro_rules! impl_dimension {
+    ($t:ident) => {
+        impl $t {
+            pub const fn zero() -> Self {
+                $t(SignedDecimal::zero())
+            }
+
+            pub const fn value(&self) -> SignedDecimal {
+                self.0
+            }
+
+            pub fn is_zero(&self) -> bool {
+                self.0.is_zero()
+            }
+        }
+
+        impl Add for $t {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                $t(self.0 + other.0)
+            }
+        }
+        forward_ref_binop!(impl Add, add for $t, $t);
+
+        impl Sub for $t {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                $t(self.0 - other.0)
+            }
+        }
+        forward_ref_binop!(impl Sub, sub for $t, $t);
+
+        // scaling by a dimensionless factor keeps the unit
+        impl Mul<SignedDecimal> for $t {
+            type Output = Self;
+
+            fn mul(self, factor: SignedDecimal) -> Self {
+                $t(self.0 * factor)
+            }
+        }
+
+        impl fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+
+impl_dimension!(Price);
+impl_dimension!(Quantity);
+impl_dimension!(Notional);
+
+impl Mul<Quantity> for Price {
+    type Output = Notional;
+
+    fn mul(self, quantity: Quantity) -> Notional {
+        Notional(self.0 * quantity.0)
+    }
+}
+
+impl Mul<Price> for Quantity {
+    type Output = Notional;
+
+    fn mul(self, price: Price) -> Notional {
+        Notional(self.0 * price.0)
+    }
+}
+
+impl Div<Quantity> for Notional {
+    type Output = Price;
+
+    fn div(self, quantity: Quantity) -> Price {
+        Price(self.0 / quantity.0)
+    }
+}
+
+impl Div<Price> for Notional {
+    type Output = Quantity;
+
+    fn div(self, price: Price) -> Quantity {
+        Quantity(self.0 / price.0)
+    }
+}
+
 fn epsilon() -> Decimal {
     Decimal::from_atomics(1u128, 8).unwrap()
 }
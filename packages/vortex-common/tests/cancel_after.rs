@@ -0,0 +1,15 @@
+use vortex_common::types::CancelAfter;
+
+#[test]
+fn cancel_after_expires_past_deadline() {
+    let switch = CancelAfter::new(30, 1_000);
+    assert!(!switch.is_expired(1_030));
+    assert!(switch.is_expired(1_031));
+}
+
+#[test]
+fn disarmed_cancel_after_never_expires() {
+    let switch = CancelAfter::new(0, 1_000);
+    assert!(!switch.is_expired(1_001));
+    assert!(!switch.is_expired(u64::MAX));
+}
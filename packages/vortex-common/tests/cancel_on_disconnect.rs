@@ -0,0 +1,30 @@
+use vortex_common::types::CancelOnDisconnect;
+
+#[test]
+fn cancel_on_disconnect_expires_after_ttl() {
+    let switch = CancelOnDisconnect {
+        ttl_epochs: 3,
+        last_refresh_epoch: 10,
+    };
+    assert!(!switch.is_expired(13));
+    assert!(switch.is_expired(14));
+}
+
+#[test]
+fn disabled_cancel_on_disconnect_never_expires() {
+    let switch = CancelOnDisconnect {
+        ttl_epochs: 0,
+        last_refresh_epoch: 10,
+    };
+    assert!(!switch.is_expired(11));
+    assert!(!switch.is_expired(i64::MAX));
+}
+
+#[test]
+fn cancel_on_disconnect_ttl_beyond_i64_never_expires() {
+    let switch = CancelOnDisconnect {
+        ttl_epochs: u64::MAX,
+        last_refresh_epoch: 0,
+    };
+    assert!(!switch.is_expired(i64::MAX));
+}
@@ -0,0 +1,49 @@
+use std::str::FromStr;
+
+use cosmwasm_std::testing::MockApi;
+use vortex_common::types::Denom;
+
+const CW20_ADDRESS: &str = "sei14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9sh9m79m";
+
+#[test]
+fn native_denom_is_its_own_base_denom() {
+    let denom = Denom::from_str("usei").unwrap();
+    assert!(!denom.is_cw20());
+    assert_eq!(denom.base_denom(), "usei");
+}
+
+#[test]
+fn cw20_denom_is_the_bare_contract_address() {
+    let denom = Denom::from_str(CW20_ADDRESS).unwrap();
+    assert!(denom.is_cw20());
+    assert_eq!(denom.as_str(), CW20_ADDRESS);
+    assert_eq!(denom.base_denom(), CW20_ADDRESS);
+}
+
+#[test]
+fn cw20_constructor_does_not_add_a_prefix() {
+    let denom = Denom::cw20(&MockApi::default(), CW20_ADDRESS).unwrap();
+    assert_eq!(denom, Denom::from_str(CW20_ADDRESS).unwrap());
+}
+
+#[test]
+fn cw20_prefix_is_not_a_denom_namespace() {
+    let denom = Denom::from_str(&format!("cw20:{}", CW20_ADDRESS)).unwrap();
+    assert!(!denom.is_cw20());
+    assert_eq!(denom.base_denom(), denom.as_str());
+}
+
+#[test]
+fn factory_and_ibc_denoms_strip_their_namespace() {
+    let factory = Denom::from_str(
+        "factory/sei1hrpna9v7vs3stzyd4z3xf00676kf78zpe2u5ksvljswn2vnjp3ys3kttyl/uusdc",
+    )
+    .unwrap();
+    assert!(factory.is_factory() && !factory.is_cw20());
+    assert_eq!(factory.base_denom(), "uusdc");
+
+    let hash = "27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+    let ibc = Denom::from_str(&format!("ibc/{}", hash)).unwrap();
+    assert!(ibc.is_ibc() && !ibc.is_cw20());
+    assert_eq!(ibc.base_denom(), hash);
+}
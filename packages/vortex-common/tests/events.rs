@@ -0,0 +1,16 @@
+use cosmwasm_std::Decimal;
+use vortex_common::events::{deposit, get_sequence, withdraw};
+use vortex_common::msg::DecimalCoin;
+
+#[test]
+fn standardized_events_carry_sequence() {
+    let coin = DecimalCoin {
+        denom: "usei".to_owned(),
+        amount: Decimal::one(),
+    };
+    assert_eq!(get_sequence(&deposit("sei1account", &coin, 7)), Some(7));
+    assert_eq!(
+        get_sequence(&withdraw("sei1account", "sei1recipient", &coin, 8)),
+        Some(8)
+    );
+}
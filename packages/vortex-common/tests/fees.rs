@@ -0,0 +1,39 @@
+use cosmwasm_std::Decimal;
+use std::str::FromStr;
+use vortex_common::types::apply_fee_discount;
+use vortex_common::utils::SignedDecimal;
+
+fn dec(value: &str) -> Decimal {
+    Decimal::from_str(value).unwrap()
+}
+
+#[test]
+fn fee_discount_leaves_rebates_untouched() {
+    let rebate = SignedDecimal::new_negative(dec("0.001"));
+    assert_eq!(apply_fee_discount(rebate, dec("0.5")), rebate);
+}
+
+#[test]
+fn zero_fee_discount_keeps_fee() {
+    let fee = SignedDecimal::new(dec("0.002"));
+    assert_eq!(apply_fee_discount(fee, Decimal::zero()), fee);
+}
+
+#[test]
+fn half_fee_discount_halves_fee() {
+    let fee = SignedDecimal::new(dec("0.002"));
+    assert_eq!(
+        apply_fee_discount(fee, dec("0.5")),
+        SignedDecimal::new(dec("0.001"))
+    );
+}
+
+#[test]
+fn full_fee_discount_waives_fee() {
+    let fee = SignedDecimal::new(dec("0.002"));
+    assert_eq!(
+        apply_fee_discount(fee, Decimal::one()),
+        SignedDecimal::zero()
+    );
+    assert_eq!(apply_fee_discount(fee, dec("1.5")), SignedDecimal::zero());
+}
@@ -0,0 +1,121 @@
+#!/usr/bin/env python3
+# Encodes the JSON sudo fixtures next to this directory into protobuf bytes with a hand-written
+# encoder, so the hex files don't depend on prost. The field tables below repeat the layout declared
+# in `proto.rs`, so the output is NOT golden: it says nothing about what the chain sends and only
+# guards `proto.rs` against accidental changes to its own layout.
+import json
+import os
+
+HERE = os.path.dirname(os.path.abspath(__file__))
+
+DIRECTIONS = {"Long": 0, "Short": 1}
+ORDER_TYPES = {"Limit": 0, "Market": 1, "Liquidation": 2, "Fokmarket": 3, "Fokmarketbyvalue": 4}
+
+
+def varint(value):
+    if value < 0:
+        value += 1 << 64
+    out = bytearray()
+    while True:
+        byte = value & 0x7F
+        value >>= 7
+        if value:
+            out.append(byte | 0x80)
+        else:
+            out.append(byte)
+            return bytes(out)
+
+
+def key(tag, wire_type):
+    return varint(tag << 3 | wire_type)
+
+
+def encode(fields):
+    # fields: (tag, kind, value); proto3 skips default scalars unless the field is optional
+    out = bytearray()
+    for tag, kind, value in fields:
+        if value is None:
+            continue
+        if kind == "varint":
+            if value:
+                out += key(tag, 0) + varint(int(value))
+        elif kind == "optional_varint":
+            out += key(tag, 0) + varint(int(value))
+        elif kind in ("string", "optional_string"):
+            raw = value.encode()
+            if raw or kind == "optional_string":
+                out += key(tag, 2) + varint(len(raw)) + raw
+        elif kind == "messages":
+            for message in value:
+                out += key(tag, 2) + varint(len(message)) + message
+        else:
+            raise ValueError(kind)
+    return bytes(out)
+
+
+def order_placement(order):
+    return encode([
+        (1, "varint", order["id"]),
+        (2, "varint", order["status"]),
+        (3, "string", order["account"]),
+        (4, "string", order["contract_address"]),
+        (5, "string", order["price_denom"]),
+        (6, "string", order["asset_denom"]),
+        (7, "string", order["price"]),
+        (8, "string", order["quantity"]),
+        (9, "varint", order["order_type"]),
+        (10, "varint", order["position_direction"]),
+        (11, "string", order["data"]),
+        (12, "optional_string", order.get("client_order_id")),
+    ])
+
+
+def deposit(info):
+    return encode([
+        (1, "string", info["account"]),
+        (2, "string", info["denom"]),
+        (3, "string", info["amount"]),
+    ])
+
+
+def settlement_entry(entry):
+    side = entry.get("liquidity_side")
+    return encode([
+        (1, "string", entry["account"]),
+        (2, "string", entry["price_denom"]),
+        (3, "string", entry["asset_denom"]),
+        (4, "string", entry["quantity"]),
+        (5, "string", entry["execution_cost_or_proceed"]),
+        (6, "string", entry["expected_cost_or_proceed"]),
+        (7, "varint", DIRECTIONS[entry["position_direction"]]),
+        (8, "varint", ORDER_TYPES[entry["order_type"]]),
+        (9, "varint", entry["order_id"]),
+        (10, "optional_varint", None if side is None else {"Maker": 0, "Taker": 1}[side]),
+    ])
+
+
+def bulk_order_placements(msg):
+    return encode([
+        (1, "messages", [order_placement(order) for order in msg["orders"]]),
+        (2, "messages", [deposit(info) for info in msg["deposits"]]),
+    ])
+
+
+def settlement(msg):
+    return encode([
+        (1, "varint", msg["epoch"]),
+        (2, "messages", [settlement_entry(entry) for entry in msg["entries"]]),
+        (3, "optional_string", msg.get("continuation_token")),
+    ])
+
+
+def main():
+    for name, encoder in [("bulk_order_placements", bulk_order_placements), ("settlement", settlement)]:
+        with open(os.path.join(HERE, "..", name + ".json")) as f:
+            msg = json.load(f)[name]
+        with open(os.path.join(HERE, name + ".hex"), "w") as f:
+            f.write(encoder(msg).hex() + "\n")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,122 @@
+use cosmwasm_std::testing::MockStorage;
+use cosmwasm_std::Decimal;
+use cw_storage_plus::Map;
+use std::str::FromStr;
+use vortex_common::error::ContractError;
+use vortex_common::types::{
+    latest_funding_snapshot, CumulativeFundingRate, FundingSnapshot, FundingSnapshotKey,
+    MarginMode, Pair, Position, PositionDirection,
+};
+use vortex_common::utils::SignedDecimal;
+
+fn signed(value: &str) -> SignedDecimal {
+    match value.strip_prefix('-') {
+        Some(abs) => SignedDecimal::new_negative(Decimal::from_str(abs).unwrap()),
+        None => SignedDecimal::new(Decimal::from_str(value).unwrap()),
+    }
+}
+
+fn position(direction: PositionDirection, last_paid_rate: &str) -> Position {
+    Position {
+        direction,
+        quantity: signed("10"),
+        total_margin_debt: SignedDecimal::zero(),
+        total_cost: signed("100"),
+        last_funding_payment_epoch: 1,
+        last_paid_funding_payment_rate: signed(last_paid_rate),
+        margin_mode: MarginMode::default(),
+        isolated_margin: SignedDecimal::zero(),
+    }
+}
+
+fn funding_rate() -> CumulativeFundingRate {
+    CumulativeFundingRate::genesis(
+        Pair {
+            price_denom: "USDC".to_owned(),
+            asset_denom: "ATOM".to_owned(),
+        },
+        1,
+    )
+    .accumulate(2, signed("0.3"), signed("-0.2"))
+}
+
+#[test]
+fn pending_funding_uses_the_index_of_the_position_side() {
+    let rate = funding_rate();
+    assert_eq!(
+        rate.pending_funding(&position(PositionDirection::Long, "0.1"))
+            .unwrap(),
+        signed("2")
+    );
+    assert_eq!(
+        rate.pending_funding(&position(PositionDirection::Short, "0"))
+            .unwrap(),
+        signed("-2")
+    );
+}
+
+#[test]
+fn both_indices_advance_to_the_same_epoch() {
+    let rate = funding_rate();
+    assert_eq!(rate.epoch, 2);
+    assert_eq!(
+        rate.snapshot(PositionDirection::Long),
+        Some(FundingSnapshot {
+            epoch: 2,
+            cumulative_rate: signed("0.3")
+        })
+    );
+    assert_eq!(
+        rate.snapshot(PositionDirection::Short),
+        Some(FundingSnapshot {
+            epoch: 2,
+            cumulative_rate: signed("-0.2")
+        })
+    );
+}
+
+#[test]
+fn unknown_direction_has_no_funding_index() {
+    let rate = funding_rate();
+    assert_eq!(rate.index(PositionDirection::Unknown), None);
+    assert_eq!(
+        rate.pending_funding(&position(PositionDirection::Unknown, "0")),
+        Err(ContractError::InvalidPositionDirection {})
+    );
+}
+
+#[test]
+fn latest_funding_snapshot_finds_snapshot_at_or_before_epoch() {
+    let snapshots: Map<FundingSnapshotKey, FundingSnapshot> = Map::new("funding_snapshots");
+    let mut storage = MockStorage::new();
+    let atom = Pair {
+        price_denom: "USDC".to_owned(),
+        asset_denom: "ATOM".to_owned(),
+    };
+    let atom2 = Pair {
+        price_denom: "USDC".to_owned(),
+        asset_denom: "ATOM2".to_owned(),
+    };
+    for (pair, epoch) in [(&atom, 2), (&atom, 5), (&atom, 9), (&atom2, 4)] {
+        let key = FundingSnapshotKey {
+            pair: pair.clone(),
+            epoch,
+        };
+        let snapshot = FundingSnapshot {
+            epoch,
+            cumulative_rate: signed(&epoch.to_string()),
+        };
+        snapshots.save(&mut storage, key, &snapshot).unwrap();
+    }
+    let latest_epoch = |pair: &Pair, epoch: i64| {
+        latest_funding_snapshot(&storage, &snapshots, pair, epoch)
+            .unwrap()
+            .map(|snapshot| snapshot.epoch)
+    };
+    assert_eq!(latest_epoch(&atom, 1), None);
+    assert_eq!(latest_epoch(&atom, 5), Some(5));
+    assert_eq!(latest_epoch(&atom, 7), Some(5));
+    assert_eq!(latest_epoch(&atom, 100), Some(9));
+    assert_eq!(latest_epoch(&atom2, 3), None);
+    assert_eq!(latest_epoch(&atom2, 4), Some(4));
+}
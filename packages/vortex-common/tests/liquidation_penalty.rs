@@ -0,0 +1,65 @@
+use cosmwasm_std::Decimal;
+use std::str::FromStr;
+use vortex_common::error::ContractError;
+use vortex_common::types::{LiquidationPenaltyCurve, LiquidationPenaltyPoint};
+
+fn decimal(value: &str) -> Decimal {
+    Decimal::from_str(value).unwrap()
+}
+
+fn curve(points: &[(&str, &str)]) -> LiquidationPenaltyCurve {
+    LiquidationPenaltyCurve {
+        points: points
+            .iter()
+            .map(|(shortfall, penalty)| LiquidationPenaltyPoint {
+                shortfall: decimal(shortfall),
+                penalty: decimal(penalty),
+            })
+            .collect(),
+    }
+}
+
+fn penalty_curve() -> LiquidationPenaltyCurve {
+    curve(&[("0.1", "0.01"), ("0.4", "0.04"), ("1", "0.1")])
+}
+
+#[test]
+fn penalty_below_the_first_point_is_the_first_penalty() {
+    assert_eq!(penalty_curve().penalty(Decimal::zero()), decimal("0.01"));
+    assert_eq!(penalty_curve().penalty(decimal("0.05")), decimal("0.01"));
+}
+
+#[test]
+fn penalty_on_a_point_is_that_point_penalty() {
+    assert_eq!(penalty_curve().penalty(decimal("0.1")), decimal("0.01"));
+    assert_eq!(penalty_curve().penalty(decimal("0.4")), decimal("0.04"));
+    assert_eq!(penalty_curve().penalty(decimal("1")), decimal("0.1"));
+}
+
+#[test]
+fn penalty_between_points_is_interpolated() {
+    assert_eq!(penalty_curve().penalty(decimal("0.2")), decimal("0.02"));
+    assert_eq!(penalty_curve().penalty(decimal("0.7")), decimal("0.07"));
+    let decreasing = curve(&[("0", "0.1"), ("1", "0.05")]);
+    assert_eq!(decreasing.penalty(decimal("0.5")), decimal("0.075"));
+}
+
+#[test]
+fn penalty_above_the_last_point_is_the_last_penalty() {
+    assert_eq!(penalty_curve().penalty(decimal("1.5")), decimal("0.1"));
+    assert_eq!(penalty_curve().penalty(decimal("100")), decimal("0.1"));
+}
+
+#[test]
+fn empty_or_unsorted_curve_is_invalid() {
+    assert_eq!(curve(&[]).penalty(decimal("0.5")), Decimal::zero());
+    assert_eq!(
+        curve(&[]).validate(),
+        Err(ContractError::InvalidLiquidationPenaltyCurve {})
+    );
+    assert_eq!(
+        curve(&[("0.4", "0.04"), ("0.4", "0.05")]).validate(),
+        Err(ContractError::InvalidLiquidationPenaltyCurve {})
+    );
+    assert_eq!(penalty_curve().validate(), Ok(()));
+}
@@ -0,0 +1,57 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{Decimal, OverflowError, OverflowOperation};
+use vortex_common::error::ContractError;
+use vortex_common::types::{MarginRatios, Market, MarketStatus, Pair};
+use vortex_common::utils::SignedDecimal;
+
+fn market() -> Market {
+    Market {
+        pair: Pair {
+            price_denom: "USDC".to_owned(),
+            asset_denom: "ATOM".to_owned(),
+        },
+        tick_size: Decimal::from_str("0.01").unwrap(),
+        lot_size: Decimal::from_str("0.1").unwrap(),
+        min_notional: Decimal::from_str("10").unwrap(),
+        max_leverage: SignedDecimal::new(Decimal::from_str("10").unwrap()),
+        margin_ratios: MarginRatios {
+            initial: Decimal::from_str("0.1").unwrap(),
+            partial: Decimal::from_str("0.075").unwrap(),
+            maintenance: Decimal::from_str("0.05").unwrap(),
+        },
+        funding_enabled: true,
+        status: MarketStatus::Active,
+        max_open_interest: None,
+        funding_settlement_denom: None,
+    }
+}
+
+#[test]
+fn order_below_min_notional_is_rejected() {
+    let err = market()
+        .validate_order(
+            Decimal::from_str("2.5").unwrap(),
+            Decimal::from_str("3").unwrap(),
+            false,
+        )
+        .unwrap_err();
+    assert!(matches!(err, ContractError::BelowMinNotional { .. }));
+    assert!(market()
+        .validate_order(
+            Decimal::from_str("2.5").unwrap(),
+            Decimal::from_str("4").unwrap(),
+            false
+        )
+        .is_ok());
+}
+
+#[test]
+fn overflowing_notional_is_an_error() {
+    let price = Decimal::from_str("1000000000").unwrap();
+    let quantity = Decimal::from_str("1000000000000").unwrap();
+    assert_eq!(
+        market().validate_order(price, quantity, false).unwrap_err(),
+        ContractError::Overflow(OverflowError::new(OverflowOperation::Mul, price, quantity))
+    );
+}
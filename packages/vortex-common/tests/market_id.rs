@@ -0,0 +1,60 @@
+use vortex_common::types::{MarketId, Pair};
+
+#[test]
+fn market_id_round_trips_plain_denoms() {
+    let market_id: MarketId = "ATOM-USDC".parse().unwrap();
+    assert_eq!(market_id.asset_denom(), "ATOM");
+    assert_eq!(market_id.price_denom(), "USDC");
+    assert_eq!(market_id.to_string(), "ATOM-USDC");
+    assert_eq!(
+        serde_json_wasm::to_string(&market_id).unwrap(),
+        r#""ATOM-USDC""#
+    );
+}
+
+#[test]
+fn market_id_escapes_separator_in_denoms() {
+    let pair = Pair {
+        price_denom: "factory/sei1creator/usd-coin".to_owned(),
+        asset_denom: "st-atom%".to_owned(),
+    };
+    let market_id = MarketId::try_from(pair.clone()).unwrap();
+    let encoded = market_id.to_string();
+    assert_eq!(encoded, "st%2Datom%25-factory/sei1creator/usd%2Dcoin");
+    let decoded: MarketId = encoded.parse().unwrap();
+    assert_eq!(decoded.into_pair(), pair);
+}
+
+#[test]
+fn ambiguous_market_id_is_rejected() {
+    assert!("ATOM".parse::<MarketId>().is_err());
+    assert!("ST-ATOM-USDC".parse::<MarketId>().is_err());
+    assert!("-USDC".parse::<MarketId>().is_err());
+}
+
+#[test]
+fn stray_percent_is_rejected() {
+    for market_id in [
+        "a%b-usdc",
+        "atom-usdc%",
+        "atom%2-usdc",
+        "atom%2d-usdc",
+        "atom-us%2Xdc",
+    ] {
+        assert!(
+            market_id.parse::<MarketId>().is_err(),
+            "{} should be rejected",
+            market_id
+        );
+    }
+}
+
+#[test]
+fn parsed_market_id_displays_as_its_input() {
+    for market_id in ["a%25b-usdc", "st%2Datom-usdc", "%25%2D-%2D%25"] {
+        assert_eq!(
+            market_id.parse::<MarketId>().unwrap().to_string(),
+            market_id
+        );
+    }
+}
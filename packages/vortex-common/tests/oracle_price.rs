@@ -0,0 +1,50 @@
+use cosmwasm_std::Decimal;
+use std::str::FromStr;
+use vortex_common::error::ContractError;
+use vortex_common::msg::OraclePrice;
+
+fn scaled(price: &str, exponent: i32) -> Result<Decimal, ContractError> {
+    OraclePrice {
+        denom: "ATOM".to_owned(),
+        price: Decimal::from_str(price).unwrap(),
+        exponent,
+        publish_time: 1_700_000_000,
+    }
+    .scaled_price()
+}
+
+fn decimal(value: &str) -> Decimal {
+    Decimal::from_str(value).unwrap()
+}
+
+#[test]
+fn positive_and_zero_exponents_scale_up() {
+    assert_eq!(scaled("12.5", 0).unwrap(), decimal("12.5"));
+    assert_eq!(scaled("12.5", 2).unwrap(), decimal("1250"));
+}
+
+#[test]
+fn negative_exponents_scale_down() {
+    assert_eq!(scaled("1250", -2).unwrap(), decimal("12.5"));
+    assert_eq!(scaled("1", -18).unwrap(), decimal("0.000000000000000001"));
+}
+
+#[test]
+fn exponents_below_decimal_precision_round_to_zero() {
+    assert_eq!(
+        scaled("123456789000000000000", -30).unwrap(),
+        decimal("0.000000000123456789")
+    );
+    assert_eq!(scaled("1", -30).unwrap(), Decimal::zero());
+    assert_eq!(scaled("1", i32::MIN).unwrap(), Decimal::zero());
+}
+
+#[test]
+fn scaled_price_that_does_not_fit_is_rejected() {
+    let invalid = Err(ContractError::InvalidOraclePrice {
+        denom: "ATOM".to_owned(),
+    });
+    assert_eq!(scaled("1", 21), invalid);
+    assert_eq!(scaled("1", 40), invalid);
+    assert_eq!(scaled("0", 40).unwrap(), Decimal::zero());
+}
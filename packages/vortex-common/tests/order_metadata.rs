@@ -0,0 +1,170 @@
+use cosmwasm_std::Decimal;
+use vortex_common::error::ContractError;
+use vortex_common::msg::OrderPlacement;
+use vortex_common::types::{
+    i32_to_time_in_force, time_in_force_to_i32, MarginMode, Order, OrderPlacementStatus, OrderType,
+    SelfTradePrevention, TimeInForce,
+};
+
+fn placement(data: &str) -> OrderPlacement {
+    OrderPlacement {
+        id: 1,
+        status: OrderPlacementStatus::Placed,
+        account: "sei1account".to_owned(),
+        contract_address: "sei1contract".to_owned(),
+        price_denom: "USDC".to_owned(),
+        asset_denom: "ATOM".to_owned(),
+        price: Decimal::one(),
+        quantity: Decimal::one(),
+        order_type: 0,
+        position_direction: 0,
+        data: data.to_owned(),
+        client_order_id: None,
+    }
+}
+
+fn offending_field(data: &str) -> Option<String> {
+    match placement(data).parse_metadata() {
+        Err(ContractError::InvalidOrderMetadata { field, .. }) => field,
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn metadata_error_names_missing_field() {
+    assert_eq!(
+        offending_field(r#"{"position_effect":"Open"}"#),
+        Some("leverage".to_owned())
+    );
+}
+
+#[test]
+fn metadata_error_names_field_with_bad_value() {
+    assert_eq!(
+        offending_field(r#"{"leverage":"lots","position_effect":"Open"}"#),
+        Some("leverage".to_owned())
+    );
+    assert_eq!(
+        offending_field(r#"{"v1":{"leverage":"1","position_effect":"Open","post_only":"yes"}}"#),
+        Some("post_only".to_owned())
+    );
+}
+
+#[test]
+fn metadata_error_names_field_with_bad_enum_variant() {
+    assert_eq!(
+        offending_field(r#"{"leverage":"1","position_effect":"Sideways"}"#),
+        Some("position_effect".to_owned())
+    );
+    assert_eq!(
+        offending_field(r#"{"v1":{"leverage":"1","position_effect":"Sideways"}}"#),
+        Some("position_effect".to_owned())
+    );
+}
+
+#[test]
+fn gtd_order_takes_expiry_from_time_in_force() {
+    let order = placement(
+        r#"{"leverage":"1","position_effect":"Open","time_in_force":{"Gtd":{"expiry_epoch":12}}}"#,
+    )
+    .to_order()
+    .unwrap();
+    assert_eq!(order.expiry_epoch, Some(12));
+    let order = placement(r#"{"leverage":"1","position_effect":"Open","time_in_force":{"Gtd":{"expiry_epoch":12}},"expiry_epoch":12}"#)
+        .to_order()
+        .unwrap();
+    assert_eq!(order.expiry_epoch, Some(12));
+}
+
+#[test]
+fn conflicting_gtd_expiry_is_rejected() {
+    let err = placement(r#"{"leverage":"1","position_effect":"Open","time_in_force":{"Gtd":{"expiry_epoch":12}},"expiry_epoch":13}"#)
+        .to_order()
+        .unwrap_err();
+    match err {
+        ContractError::InvalidOrderMetadata { field, .. } => {
+            assert_eq!(field, Some("expiry_epoch".to_owned()))
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+}
+
+fn fok_placement(data: &str) -> OrderPlacement {
+    OrderPlacement {
+        order_type: 3,
+        ..placement(data)
+    }
+}
+
+#[test]
+fn fok_market_order_defaults_to_fok_time_in_force() {
+    let order = fok_placement(r#"{"leverage":"1","position_effect":"Open"}"#)
+        .to_order()
+        .unwrap();
+    assert_eq!(order.order_type, OrderType::Fokmarket);
+    assert_eq!(order.time_in_force, TimeInForce::Fok);
+}
+
+#[test]
+fn fok_market_order_rejects_other_time_in_force() {
+    let err = fok_placement(r#"{"leverage":"1","position_effect":"Open","time_in_force":"Ioc"}"#)
+        .to_order()
+        .unwrap_err();
+    match err {
+        ContractError::InvalidOrderMetadata { field, .. } => {
+            assert_eq!(field, Some("time_in_force".to_owned()))
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+}
+
+#[test]
+fn order_serialized_before_time_in_force_still_parses() {
+    let order: Order = serde_json_wasm::from_str(
+        r#"{"id":1,"account":"sei1account","price_denom":"USDC","asset_denom":"ATOM","price":{"decimal":"1","negative":false},"quantity":{"decimal":"2","negative":false},"remaining_quantity":{"decimal":"2","negative":false},"direction":"Long","effect":"Open","leverage":{"decimal":"1","negative":false},"order_type":"Limit"}"#,
+    )
+    .unwrap();
+    assert_eq!(order.time_in_force, TimeInForce::Gtc);
+    assert!(!order.post_only && !order.reduce_only);
+    assert_eq!(order.self_trade_prevention, SelfTradePrevention::Allow);
+    assert_eq!(order.margin_mode, MarginMode::default());
+    assert_eq!(order.expiry_epoch, None);
+}
+
+#[test]
+fn time_in_force_wire_codes_round_trip() {
+    for time_in_force in [
+        TimeInForce::Gtc,
+        TimeInForce::Ioc,
+        TimeInForce::Fok,
+        TimeInForce::Gtd { expiry_epoch: 7 },
+    ] {
+        assert_eq!(
+            i32_to_time_in_force(time_in_force_to_i32(time_in_force), 7),
+            time_in_force
+        );
+    }
+    assert_eq!(i32_to_time_in_force(42, 7), TimeInForce::Unknown);
+}
+
+#[test]
+fn unknown_enum_values_are_rejected() {
+    let err = placement(r#"{"leverage":"1","position_effect":"Unknown"}"#)
+        .to_order()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnknownEnumValue {
+            field: "position_effect".to_owned()
+        }
+    );
+    let err = placement(r#"{"leverage":"1","position_effect":"Open","time_in_force":"Unknown"}"#)
+        .to_order()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnknownEnumValue {
+            field: "time_in_force".to_owned()
+        }
+    );
+}
@@ -0,0 +1,21 @@
+use vortex_common::types::OrderPlacementStatus;
+
+#[test]
+fn known_status_codes_round_trip() {
+    for code in 0..=6 {
+        let status: OrderPlacementStatus = serde_json_wasm::from_str(&code.to_string()).unwrap();
+        assert!(!matches!(status, OrderPlacementStatus::Unknown(_)));
+        assert_eq!(
+            serde_json_wasm::to_string(&status).unwrap(),
+            code.to_string()
+        );
+    }
+}
+
+#[test]
+fn unknown_status_code_is_kept() {
+    let status: OrderPlacementStatus = serde_json_wasm::from_str("42").unwrap();
+    assert_eq!(status, OrderPlacementStatus::Unknown(42));
+    assert_eq!(serde_json_wasm::to_string(&status).unwrap(), "42");
+    assert_eq!(i32::from(OrderPlacementStatus::from(-7)), -7);
+}
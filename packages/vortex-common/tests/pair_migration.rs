@@ -0,0 +1,79 @@
+use cosmwasm_std::testing::MockStorage;
+use cosmwasm_std::{Addr, Storage};
+use cw_storage_plus::Map;
+use vortex_common::types::{migrate_legacy_composite_pair_keys, Pair};
+
+fn pair(price_denom: &str, asset_denom: &str) -> Pair {
+    Pair {
+        price_denom: price_denom.to_owned(),
+        asset_denom: asset_denom.to_owned(),
+    }
+}
+
+// `(Addr, Pair)` key as stored before pairs became length-prefixed composite keys
+fn legacy_key(namespace: &str, account: &str, pair: &Pair) -> Vec<u8> {
+    let mut key = (namespace.len() as u16).to_be_bytes().to_vec();
+    key.extend_from_slice(namespace.as_bytes());
+    key.extend_from_slice(&(account.len() as u16).to_be_bytes());
+    key.extend_from_slice(account.as_bytes());
+    key.extend_from_slice(&pair.legacy_key_bytes());
+    key
+}
+
+#[test]
+fn composite_pair_keys_are_migrated() {
+    let mut storage = MockStorage::new();
+    let atom = pair("USDC", "ATOM");
+    let sei = pair("USDC", "SEI");
+    storage.set(&legacy_key("positions", "sei1alice", &atom), b"1");
+    storage.set(&legacy_key("positions", "sei1alice", &sei), b"2");
+    storage.set(&legacy_key("positions", "sei1bob", &atom), b"3");
+    // an unrelated namespace sharing the prefix must be left alone
+    storage.set(&legacy_key("positions_v0", "sei1bob", &atom), b"4");
+
+    let positions: Map<(Addr, Pair), u64> = Map::new("positions_v2");
+    let migrated = migrate_legacy_composite_pair_keys(
+        &mut storage,
+        "positions",
+        &positions,
+        &[atom.clone(), sei.clone()],
+    )
+    .unwrap();
+    assert_eq!(migrated, 3);
+
+    let alice = Addr::unchecked("sei1alice");
+    let bob = Addr::unchecked("sei1bob");
+    assert_eq!(
+        positions
+            .load(&storage, (alice.clone(), atom.clone()))
+            .unwrap(),
+        1
+    );
+    assert_eq!(positions.load(&storage, (alice, sei)).unwrap(), 2);
+    assert_eq!(positions.load(&storage, (bob, atom.clone())).unwrap(), 3);
+    assert_eq!(
+        storage.get(&legacy_key("positions", "sei1bob", &atom)),
+        None
+    );
+    assert_eq!(
+        storage.get(&legacy_key("positions_v0", "sei1bob", &atom)),
+        Some(b"4".to_vec())
+    );
+}
+
+#[test]
+fn composite_migration_fails_for_unknown_pair() {
+    let mut storage = MockStorage::new();
+    storage.set(
+        &legacy_key("positions", "sei1alice", &pair("USDC", "ATOM")),
+        b"1",
+    );
+    let positions: Map<(Addr, Pair), u64> = Map::new("positions_v2");
+    assert!(migrate_legacy_composite_pair_keys(
+        &mut storage,
+        "positions",
+        &positions,
+        &[pair("USDC", "SEI")],
+    )
+    .is_err());
+}
@@ -0,0 +1,94 @@
+use cosmwasm_std::Decimal;
+use std::str::FromStr;
+use vortex_common::types::{MarginMode, Position, PositionDirection};
+use vortex_common::utils::SignedDecimal;
+
+fn signed(value: &str) -> SignedDecimal {
+    match value.strip_prefix('-') {
+        Some(abs) => SignedDecimal::new_negative(Decimal::from_str(abs).unwrap()),
+        None => SignedDecimal::new(Decimal::from_str(value).unwrap()),
+    }
+}
+
+fn decimal(value: &str) -> Decimal {
+    Decimal::from_str(value).unwrap()
+}
+
+fn position(direction: PositionDirection, quantity: &str, cost: &str, debt: &str) -> Position {
+    Position {
+        direction,
+        quantity: signed(quantity),
+        total_margin_debt: signed(debt),
+        total_cost: signed(cost),
+        last_funding_payment_epoch: 0,
+        last_paid_funding_payment_rate: SignedDecimal::zero(),
+        margin_mode: MarginMode::Cross,
+        isolated_margin: SignedDecimal::zero(),
+    }
+}
+
+#[test]
+fn long_position_gains_when_the_price_rises() {
+    let long = position(PositionDirection::Long, "10", "100", "60");
+    let mark_price = decimal("12");
+    assert_eq!(long.notional(mark_price), signed("120"));
+    assert_eq!(long.average_entry_price(), Some(signed("10")));
+    assert_eq!(long.unrealized_pnl(mark_price), signed("20"));
+    assert_eq!(long.equity(mark_price), signed("60"));
+    assert_eq!(long.margin_ratio(mark_price), Some(signed("0.5")));
+    assert_eq!(long.unrealized_pnl(decimal("8")), signed("-20"));
+}
+
+#[test]
+fn short_position_gains_when_the_price_falls() {
+    let short = position(PositionDirection::Short, "10", "100", "0");
+    let mark_price = decimal("8");
+    assert_eq!(short.notional(mark_price), signed("80"));
+    assert_eq!(short.average_entry_price(), Some(signed("10")));
+    assert_eq!(short.unrealized_pnl(mark_price), signed("20"));
+    assert_eq!(short.equity(mark_price), signed("120"));
+    assert_eq!(short.margin_ratio(mark_price), Some(signed("1.5")));
+    assert_eq!(short.unrealized_pnl(decimal("12")), signed("-20"));
+}
+
+#[test]
+fn empty_position_has_no_entry_price_or_margin_ratio() {
+    let empty = position(PositionDirection::Long, "0", "0", "0");
+    let mark_price = decimal("10");
+    assert_eq!(empty.notional(mark_price), SignedDecimal::zero());
+    assert_eq!(empty.average_entry_price(), None);
+    assert_eq!(empty.unrealized_pnl(mark_price), SignedDecimal::zero());
+    assert_eq!(empty.margin_ratio(mark_price), None);
+    assert!(!empty.is_liquidatable(mark_price, decimal("0.05")));
+}
+
+#[test]
+fn position_is_liquidatable_strictly_below_maintenance() {
+    // equity 10 on a notional of 100: a margin ratio of exactly 0.1
+    let long = position(PositionDirection::Long, "10", "100", "90");
+    assert_eq!(long.margin_ratio(decimal("10")), Some(signed("0.1")));
+    assert!(!long.is_liquidatable(decimal("10"), decimal("0.1")));
+    assert!(long.is_liquidatable(decimal("10"), decimal("0.100000000000000001")));
+    // equity 9 on a notional of 99
+    assert!(long.is_liquidatable(decimal("9.9"), decimal("0.1")));
+
+    let short = position(PositionDirection::Short, "10", "100", "90");
+    assert_eq!(short.margin_ratio(decimal("10")), Some(signed("0.1")));
+    assert!(!short.is_liquidatable(decimal("10"), decimal("0.1")));
+    assert!(short.is_liquidatable(decimal("10.1"), decimal("0.1")));
+}
+
+#[test]
+fn margin_ratio_is_not_truncated_below_maintenance() {
+    // equity 60 on a notional of 120 is exactly half
+    let long = position(PositionDirection::Long, "10", "100", "60");
+    assert_eq!(long.margin_ratio(decimal("12")), Some(signed("0.5")));
+    assert!(!long.is_liquidatable(decimal("12"), decimal("0.5")));
+
+    let underwater = position(PositionDirection::Long, "10", "100", "90");
+    assert_eq!(
+        underwater.margin_ratio(decimal("8")),
+        Some(signed("-0.125"))
+    );
+    assert!(underwater.is_liquidatable(decimal("8"), decimal("0.05")));
+}
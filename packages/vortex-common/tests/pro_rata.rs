@@ -0,0 +1,67 @@
+use cosmwasm_std::Decimal;
+use std::str::FromStr;
+use vortex_common::error::ContractError;
+use vortex_common::utils::{allocate_pro_rata, SignedDecimal};
+
+fn signed(value: &str) -> SignedDecimal {
+    match value.strip_prefix('-') {
+        Some(abs) => SignedDecimal::new_negative(Decimal::from_str(abs).unwrap()),
+        None => SignedDecimal::new(Decimal::from_str(value).unwrap()),
+    }
+}
+
+fn weights(values: &[&str]) -> Vec<SignedDecimal> {
+    values.iter().map(|value| signed(value)).collect()
+}
+
+#[test]
+fn shares_are_proportional_to_weights() {
+    assert_eq!(
+        allocate_pro_rata(signed("100"), &weights(&["1", "3"])).unwrap(),
+        weights(&["25", "75"])
+    );
+}
+
+#[test]
+fn rounding_dust_goes_to_the_largest_weight() {
+    let shares = allocate_pro_rata(signed("1"), &weights(&["1", "1", "1"])).unwrap();
+    assert_eq!(shares[0], signed("0.333333333333333334"));
+    assert_eq!(shares[1], signed("0.333333333333333333"));
+    assert_eq!(shares[2], signed("0.333333333333333333"));
+    let allocated = shares
+        .iter()
+        .fold(SignedDecimal::zero(), |acc, share| acc + *share);
+    assert_eq!(allocated, signed("1"));
+}
+
+#[test]
+fn negative_weights_of_the_same_sign_are_allocated() {
+    assert_eq!(
+        allocate_pro_rata(signed("10"), &weights(&["-1", "-4", "0"])).unwrap(),
+        weights(&["2", "8", "0"])
+    );
+}
+
+#[test]
+fn zero_weight_sum_is_rejected() {
+    assert_eq!(
+        allocate_pro_rata(signed("1"), &weights(&["0", "0"])),
+        Err(ContractError::InvalidAllocationWeights {})
+    );
+    assert_eq!(
+        allocate_pro_rata(signed("1"), &[]),
+        Err(ContractError::InvalidAllocationWeights {})
+    );
+}
+
+#[test]
+fn mixed_sign_weights_are_rejected() {
+    assert_eq!(
+        allocate_pro_rata(signed("1"), &weights(&["1", "-1"])),
+        Err(ContractError::InvalidAllocationWeights {})
+    );
+    assert_eq!(
+        allocate_pro_rata(signed("1"), &weights(&["2", "-1"])),
+        Err(ContractError::InvalidAllocationWeights {})
+    );
+}
@@ -0,0 +1,74 @@
+// Regression tests for the protobuf layout declared in `proto.rs`. These are NOT golden fixtures: the
+// `.hex` files are produced by `fixtures/proto/encode.py` from this crate's own JSON fixtures and
+// field tables, so they only catch accidental changes to the layout, not differences from the chain.
+// Bytes captured from the chain are checked by `captured_payloads_decode`.
+#![cfg(feature = "protobuf")]
+
+use vortex_common::msg::SudoMsg;
+use vortex_common::proto::{
+    decode_bulk_order_placements, decode_finalize_block, decode_settlement,
+};
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    let hex = hex.trim();
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("fixture should be valid hex"))
+        .collect()
+}
+
+fn parse(fixture: &str) -> SudoMsg {
+    serde_json_wasm::from_str(fixture).expect("fixture should deserialize into SudoMsg")
+}
+
+#[test]
+fn bulk_order_placements_layout_is_unchanged() {
+    let bytes = from_hex(include_str!("fixtures/proto/bulk_order_placements.hex"));
+    assert_eq!(
+        decode_bulk_order_placements(&bytes).unwrap(),
+        parse(include_str!("fixtures/bulk_order_placements.json"))
+    );
+}
+
+#[test]
+fn settlement_layout_is_unchanged() {
+    let bytes = from_hex(include_str!("fixtures/proto/settlement.hex"));
+    assert_eq!(
+        decode_settlement(&bytes).unwrap(),
+        parse(include_str!("fixtures/settlement.json"))
+    );
+}
+
+#[test]
+fn truncated_payload_is_rejected() {
+    let bytes = from_hex(include_str!("fixtures/proto/settlement.hex"));
+    assert!(decode_settlement(&bytes[..bytes.len() - 1]).is_err());
+}
+
+// `<payload>_captured.hex` files hold bytes captured from the chain; each one must come with a
+// `<payload>_captured.chain_version` file naming the sei-chain version it was captured from
+#[test]
+fn captured_payloads_decode() {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/proto");
+    for entry in std::fs::read_dir(&dir).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_str().unwrap().to_owned();
+        let payload = match name.strip_suffix("_captured.hex") {
+            Some(payload) => payload.to_owned(),
+            None => continue,
+        };
+        let chain_version = std::fs::read_to_string(path.with_extension("chain_version"))
+            .unwrap_or_else(|_| panic!("{} has no chain_version file", name));
+        assert!(!chain_version.trim().is_empty());
+        let bytes = from_hex(&std::fs::read_to_string(&path).unwrap());
+        let decoded = match payload.as_str() {
+            "bulk_order_placements" => decode_bulk_order_placements(&bytes),
+            "settlement" => decode_settlement(&bytes),
+            "finalize_block" => decode_finalize_block(&bytes),
+            other => panic!("unknown payload {}", other),
+        };
+        if let Err(err) = decoded {
+            panic!("{} doesn't decode: {}", name, err);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::str::FromStr;
+
+use cosmwasm_std::Decimal;
+use vortex_common::types::{RewardsAccumulator, RewardsConfig};
+
+fn config() -> RewardsConfig {
+    RewardsConfig {
+        reward_denom: "usei".to_owned(),
+        emission_per_epoch: Decimal::from_str("1000").unwrap(),
+        maker_share: Decimal::from_str("0.6").unwrap(),
+        taker_share: Decimal::from_str("0.4").unwrap(),
+    }
+}
+
+fn accumulator(maker_volume: &str, taker_volume: &str) -> RewardsAccumulator {
+    RewardsAccumulator {
+        epoch: 1,
+        maker_volume: Decimal::from_str(maker_volume).unwrap(),
+        taker_volume: Decimal::from_str(taker_volume).unwrap(),
+    }
+}
+
+#[test]
+fn rewards_are_split_by_volume_share() {
+    let rewards = accumulator("25", "10")
+        .accrued_rewards(&accumulator("100", "40"), &config())
+        .unwrap();
+    // 600 * 25 / 100 + 400 * 10 / 40
+    assert_eq!(rewards, Decimal::from_str("250").unwrap());
+}
+
+#[test]
+fn side_without_volume_earns_nothing() {
+    let rewards = accumulator("0", "10")
+        .accrued_rewards(&accumulator("0", "40"), &config())
+        .unwrap();
+    assert_eq!(rewards, Decimal::from_str("100").unwrap());
+}
+
+#[test]
+fn large_volumes_do_not_overflow() {
+    let rewards = accumulator("1000000000000000000", "0")
+        .accrued_rewards(&accumulator("2000000000000000000", "0"), &config())
+        .unwrap();
+    assert_eq!(rewards, Decimal::from_str("300").unwrap());
+}
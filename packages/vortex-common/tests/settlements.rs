@@ -0,0 +1,44 @@
+use cosmwasm_std::Decimal;
+use vortex_common::msg::{net_settlements, SettlementEntry};
+use vortex_common::types::{LiquiditySide, OrderType, PositionDirection};
+
+fn entry(order_id: u64, quantity: u64, liquidity_side: Option<LiquiditySide>) -> SettlementEntry {
+    SettlementEntry {
+        account: "sei1account".to_owned(),
+        price_denom: "USDC".to_owned(),
+        asset_denom: "ATOM".to_owned(),
+        quantity: Decimal::from_atomics(quantity, 0).unwrap(),
+        execution_cost_or_proceed: Decimal::from_atomics(quantity * 10, 0).unwrap(),
+        expected_cost_or_proceed: Decimal::from_atomics(quantity * 10, 0).unwrap(),
+        position_direction: PositionDirection::Long,
+        order_type: OrderType::Limit,
+        order_id,
+        liquidity_side,
+    }
+}
+
+#[test]
+fn net_settlements_keeps_maker_and_taker_fills_apart() {
+    let netted = net_settlements(vec![
+        entry(1, 2, Some(LiquiditySide::Maker)),
+        entry(2, 3, Some(LiquiditySide::Taker)),
+        entry(3, 4, Some(LiquiditySide::Maker)),
+    ]);
+    assert_eq!(netted.len(), 2);
+    assert_eq!(netted[0].liquidity_side, Some(LiquiditySide::Maker));
+    assert_eq!(netted[0].quantity, Decimal::from_atomics(6u64, 0).unwrap());
+    assert_eq!(netted[0].order_ids, vec![1, 3]);
+    assert_eq!(netted[1].liquidity_side, Some(LiquiditySide::Taker));
+    assert_eq!(netted[1].quantity, Decimal::from_atomics(3u64, 0).unwrap());
+    assert_eq!(netted[1].order_ids, vec![2]);
+}
+
+#[test]
+fn net_settlements_keeps_order_types_apart() {
+    let mut liquidation = entry(2, 3, None);
+    liquidation.order_type = OrderType::Liquidation;
+    let netted = net_settlements(vec![entry(1, 2, None), liquidation]);
+    assert_eq!(netted.len(), 2);
+    assert_eq!(netted[0].order_type, OrderType::Limit);
+    assert_eq!(netted[1].order_type, OrderType::Liquidation);
+}
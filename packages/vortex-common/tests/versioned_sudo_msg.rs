@@ -0,0 +1,29 @@
+use vortex_common::error::ContractError;
+use vortex_common::msg::{SudoMsg, SUDO_MSG_VERSION};
+
+fn epoch_end() -> SudoMsg {
+    SudoMsg::EpochEnd {
+        epoch: 7,
+        epoch_start_time: 1_700_000_000,
+        epoch_end_time: 1_700_003_600,
+    }
+}
+
+#[test]
+fn versioned_message_unwraps_to_the_original() {
+    let versioned = SudoMsg::versioned(&epoch_end()).unwrap();
+    match &versioned {
+        SudoMsg::Versioned { version, .. } => assert_eq!(*version, SUDO_MSG_VERSION),
+        other => panic!("unexpected sudo msg {:?}", other),
+    }
+    assert_eq!(versioned.into_latest().unwrap(), epoch_end());
+}
+
+#[test]
+fn versioned_rejects_an_envelope() {
+    let versioned = SudoMsg::versioned(&epoch_end()).unwrap();
+    assert!(matches!(
+        SudoMsg::versioned(&versioned),
+        Err(ContractError::InvalidSudoMsg { .. })
+    ));
+}